@@ -4,7 +4,6 @@ mod dialog;
 mod event;
 mod notification;
 mod os;
-mod tauri_log;
 mod window;
 mod global_shortcut;
 
@@ -14,7 +13,6 @@ use std::future::Future;
 use std::panic;
 use sycamore::prelude::*;
 use sycamore::suspense::Suspense;
-use tauri_log::TauriLogger;
 
 #[cfg(feature = "ci")]
 async fn exit_with_error(e: String) {
@@ -141,11 +139,10 @@ pub async fn Terminate<'a, G: Html>(cx: Scope<'a>) -> View<G> {
     }
 }
 
-static LOGGER: TauriLogger = TauriLogger;
-
 fn main() {
-    log::set_logger(&LOGGER)
-        .map(|()| log::set_max_level(LevelFilter::Trace))
+    tauri_sys::log::LoggerBuilder::new()
+        .set_level(LevelFilter::Trace)
+        .install()
         .unwrap();
 
     panic::set_hook(Box::new(|info| {
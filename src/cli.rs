@@ -0,0 +1,179 @@
+//! Parse arguments from your Command Line Interface.
+//!
+//! The CLI plugin must be installed and configured.
+//! ```rust
+//! tauri::Builder::default()
+//!   .plugin(tauri_plugin_cli::init())
+//!   .run(tauri::generate_context!())
+//! ```
+//!
+//! # Deserializing matches
+//!
+//! Instead of walking the [`CliMatches`] by hand, any type implementing [`serde::Deserialize`] can be parsed from the matches
+//! through the [`FromCliMatches`] trait. Arguments are keyed by their name, their values are:
+//!
+//! - `bool` for flags,
+//! - `String` for arguments taking a single value,
+//! - `Vec<String>` for arguments taking multiple values,
+//! - missing for arguments that were not passed, so they should be modeled as `Option`s.
+//!
+//! The subcommand is stored under the `subcommand` key as an externally tagged enum.
+//!
+//! ```rust,no_run
+//! use serde::Deserialize;
+//! use std::path::PathBuf;
+//! use tauri_sys::cli::get_matches_as;
+//!
+//! #[derive(Deserialize)]
+//! struct Args {
+//!     verbose: bool,
+//!     file: Option<PathBuf>,
+//!     subcommand: Option<Subcommand>,
+//! }
+//!
+//! #[derive(Deserialize)]
+//! #[serde(rename_all = "kebab-case")]
+//! enum Subcommand {
+//!     Open { paths: Vec<PathBuf> },
+//!     Version {},
+//! }
+//!
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let args = get_matches_as::<Args>().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// The value of a matched argument.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum ArgValue {
+    /// The argument is a flag.
+    Flag(bool),
+    /// The argument takes a single value.
+    Value(String),
+    /// The argument takes multiple values.
+    Values(Vec<String>),
+}
+
+/// A matched argument.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ArgMatch {
+    /// The value of the argument, `None` if the argument takes a value but was not passed.
+    #[serde(default)]
+    pub value: Option<ArgValue>,
+    /// How many times the argument was passed.
+    pub occurrences: u32,
+}
+
+/// A matched subcommand.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct SubcommandMatch {
+    /// The name of the subcommand.
+    pub name: String,
+    /// The matches of the subcommand.
+    pub matches: CliMatches,
+}
+
+/// The arguments and subcommand the app was launched with.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct CliMatches {
+    /// The matched arguments, keyed by their name.
+    pub args: HashMap<String, ArgMatch>,
+    /// The matched subcommand.
+    pub subcommand: Option<Box<SubcommandMatch>>,
+}
+
+impl CliMatches {
+    /// Parses the matches into any type implementing [`FromCliMatches`].
+    pub fn parse<T: FromCliMatches>(&self) -> crate::Result<T> {
+        T::from_cli_matches(self)
+    }
+
+    fn to_json(&self) -> Value {
+        let mut map = Map::new();
+
+        for (name, arg) in &self.args {
+            let value = match &arg.value {
+                Some(ArgValue::Flag(flag)) => Value::Bool(*flag),
+                Some(ArgValue::Value(value)) => Value::String(value.clone()),
+                Some(ArgValue::Values(values)) => {
+                    Value::Array(values.iter().cloned().map(Value::String).collect())
+                }
+                None => continue,
+            };
+
+            map.insert(name.clone(), value);
+        }
+
+        if let Some(subcommand) = &self.subcommand {
+            let mut tagged = Map::new();
+            tagged.insert(subcommand.name.clone(), subcommand.matches.to_json());
+
+            map.insert("subcommand".to_string(), Value::Object(tagged));
+        }
+
+        Value::Object(map)
+    }
+}
+
+/// Types that can be created from [`CliMatches`].
+///
+/// This trait is implemented for all types implementing [`serde::Deserialize`],
+/// see the [module level documentation](self) for how the matches are represented.
+pub trait FromCliMatches: Sized {
+    fn from_cli_matches(matches: &CliMatches) -> crate::Result<Self>;
+}
+
+impl<T: DeserializeOwned> FromCliMatches for T {
+    fn from_cli_matches(matches: &CliMatches) -> crate::Result<Self> {
+        serde_json::from_value(matches.to_json()).map_err(|e| crate::Error::Serde(e.to_string()))
+    }
+}
+
+/// Parse the arguments provided to the current process and get the matches using the configuration defined [`tauri.cli`](https://tauri.app/v1/api/config/#tauriconfig.cli) in `tauri.conf.json`
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tauri_sys::cli::{get_matches, ArgValue};
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let matches = get_matches().await?;
+///
+/// if let Some(subcommand) = matches.subcommand {
+///     println!("Running subcommand {}", subcommand.name);
+/// }
+///
+/// if let Some(ArgValue::Value(file)) = matches.args.get("file").and_then(|arg| arg.value.as_ref()) {
+///     println!("Opening {}", file);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn get_matches() -> crate::Result<CliMatches> {
+    let raw = inner::getMatches().await?;
+
+    Ok(serde_wasm_bindgen::from_value(raw)?)
+}
+
+/// Parse the arguments provided to the current process and deserialize the matches into `T`.
+///
+/// See the [module level documentation](self) for details.
+pub async fn get_matches_as<T: FromCliMatches>() -> crate::Result<T> {
+    get_matches().await?.parse()
+}
+
+mod inner {
+    use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
+
+    #[wasm_bindgen(module = "/src/cli.js")]
+    extern "C" {
+        #[wasm_bindgen(catch)]
+        pub async fn getMatches() -> Result<JsValue, JsValue>;
+    }
+}
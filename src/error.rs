@@ -10,9 +10,30 @@ pub enum Error {
     #[cfg(any(feature = "event", feature = "window"))]
     #[error("Oneshot cancelled: {0}")]
     OneshotCanceled(#[from] futures::channel::oneshot::Canceled),
+    #[cfg(feature = "event")]
+    #[error("timed out")]
+    Timeout,
     #[cfg(feature = "fs")]
     #[error("could not convert path to string")]
     Utf8(PathBuf),
+    #[cfg(feature = "fs")]
+    #[error("path must be relative and must not contain `..`: {0:?}")]
+    InvalidScopedPath(PathBuf),
+    #[cfg(feature = "fs")]
+    #[error("paths must share a base directory, found {0:?} and {1:?}")]
+    BaseDirectoryMismatch(crate::fs::BaseDirectory, crate::fs::BaseDirectory),
+    #[cfg(feature = "fs")]
+    #[error("invalid glob pattern {0:?}: {1}")]
+    InvalidGlob(String, String),
+    #[cfg(feature = "fs")]
+    #[error("{0:?} already exists")]
+    AlreadyExists(PathBuf),
+    #[cfg(feature = "fs")]
+    #[error("cannot copy {0:?} into itself: {1:?}")]
+    DestinationInsideSource(PathBuf, PathBuf),
+    #[cfg(feature = "fs")]
+    #[error("failed to write whole buffer, {0} bytes left")]
+    WriteZero(usize),
 }
 
 impl From<serde_wasm_bindgen::Error> for Error {
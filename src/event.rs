@@ -2,10 +2,10 @@
 
 use futures::{
     channel::{mpsc, oneshot},
-    Future, FutureExt, Stream, StreamExt,
+    future, stream, Future, FutureExt, Stream, StreamExt,
 };
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use std::fmt::Debug;
+use std::{cell::RefCell, collections::VecDeque, fmt::Debug, rc::Rc, task::Waker, time::Duration};
 use wasm_bindgen::{prelude::Closure, JsValue};
 
 #[derive(Debug, Clone, PartialEq, Deserialize)]
@@ -76,10 +76,75 @@ pub async fn emit<T: Serialize>(
     Ok(())
 }
 
+/// An event with a fixed name and payload type.
+///
+/// Implementing this trait pairs the event name with its payload at compile time,
+/// so renaming the event or changing its payload on one side can't go unnoticed on the other.
+/// Since the trait has no dependencies on the frontend, the event catalog can be defined once in a crate shared with the backend.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use futures::StreamExt;
+/// use serde::{Deserialize, Serialize};
+/// use tauri_sys::event::{emit_typed, listen_typed, EventOptions, TypedEvent};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Progress {
+///     done: u32,
+///     total: u32,
+/// }
+///
+/// struct DownloadProgress;
+///
+/// impl TypedEvent for DownloadProgress {
+///     const NAME: &'static str = "download-progress";
+///     type Payload = Progress;
+/// }
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut events = listen_typed::<DownloadProgress>(EventOptions::new()).await?;
+///
+/// emit_typed::<DownloadProgress>(&Progress { done: 0, total: 10 }, EventOptions::new()).await?;
+///
+/// while let Some(event) = events.next().await {
+///     log::debug!("{}/{}", event.payload.done, event.payload.total);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub trait TypedEvent {
+    /// The name of the event.
+    const NAME: &'static str;
+    /// The payload of the event.
+    type Payload: Serialize + DeserializeOwned + 'static;
+}
+
+/// Emits a [`TypedEvent`] to the backend.
+#[inline(always)]
+pub async fn emit_typed<E: TypedEvent>(
+    payload: &E::Payload,
+    options: EventOptions,
+) -> crate::Result<()> {
+    emit(E::NAME, payload, options).await
+}
+
+/// Listen to a [`TypedEvent`] from the backend.
+///
+/// The returned Future will automatically clean up it's underlying event listener when dropped, so no manual unlisten function needs to be called.
+#[inline(always)]
+pub async fn listen_typed<E: TypedEvent>(
+    options: EventOptions,
+) -> crate::Result<impl Stream<Item = Event<E::Payload>>> {
+    listen(E::NAME, options).await
+}
+
 /// Listen to an event from the backend.
 ///
 /// The returned Future will automatically clean up it's underlying event listener when dropped, so no manual unlisten function needs to be called.
 ///
+/// Events whose payload can't be deserialized into `T` are logged and skipped, use [`try_listen`] to receive them as errors instead.
+///
 /// # Example
 ///
 /// ```rust,no_run
@@ -104,7 +169,9 @@ where
     let (tx, rx) = mpsc::unbounded::<Event<T>>();
 
     let closure = Closure::<dyn FnMut(JsValue)>::new(move |raw| {
-        let _ = tx.unbounded_send(serde_wasm_bindgen::from_value(raw).unwrap());
+        if let Some(event) = deserialize_or_log(raw) {
+            let _ = tx.unbounded_send(event);
+        }
     });
     let unlisten = inner::listen(event, &closure, serde_wasm_bindgen::to_value(&options)?).await?;
     closure.forget();
@@ -115,6 +182,278 @@ where
     })
 }
 
+/// Listen to multiple events from the backend, merging them into a single stream.
+///
+/// The name of the originating event is preserved in [`Event::event`].
+/// The returned Future will automatically clean up all underlying event listeners when dropped, so no manual unlisten function needs to be called.
+///
+/// Tauri dispatches events by their exact name and has no wildcard subscription, so every name has to be known upfront,
+/// e.g. by building the names of a job from its id.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use futures::StreamExt;
+/// use tauri_sys::event::{listen_many, EventOptions};
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let job = 123;
+/// let progress = format!("job:{}:progress", job);
+/// let done = format!("job:{}:done", job);
+///
+/// let mut events = listen_many::<u32>(&[&progress, &done], EventOptions::new()).await?;
+///
+/// while let Some(event) = events.next().await {
+///     if event.event == done {
+///         break;
+///     }
+///
+///     log::debug!("Job {} is at {}%", job, event.payload);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn listen_many<T>(
+    events: &[&str],
+    options: EventOptions,
+) -> crate::Result<impl Stream<Item = Event<T>>>
+where
+    T: DeserializeOwned + 'static,
+{
+    let streams = future::try_join_all(
+        events
+            .iter()
+            .map(|event| listen::<T>(event, options.clone())),
+    )
+    .await?;
+
+    Ok(stream::select_all(streams.into_iter().map(Box::pin)))
+}
+
+/// Listen to an event from the backend, yielding an error for every event whose payload can't be deserialized into `T`.
+///
+/// The returned Future will automatically clean up it's underlying event listener when dropped, so no manual unlisten function needs to be called.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use futures::StreamExt;
+/// use tauri_sys::event::{try_listen, EventOptions};
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut events = try_listen::<u32>("progress", EventOptions::new()).await?;
+///
+/// while let Some(event) = events.next().await {
+///     match event {
+///         Ok(event) => log::debug!("Progress {}", event.payload),
+///         Err(err) => log::warn!("Malformed progress event: {}", err),
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[inline(always)]
+pub async fn try_listen<T>(
+    event: &str,
+    options: EventOptions,
+) -> crate::Result<impl Stream<Item = crate::Result<Event<T>>>>
+where
+    T: DeserializeOwned + 'static,
+{
+    let (tx, rx) = mpsc::unbounded::<crate::Result<Event<T>>>();
+
+    let closure = Closure::<dyn FnMut(JsValue)>::new(move |raw| {
+        let _ = tx.unbounded_send(serde_wasm_bindgen::from_value(raw).map_err(Into::into));
+    });
+    let unlisten = inner::listen(event, &closure, serde_wasm_bindgen::to_value(&options)?).await?;
+    closure.forget();
+
+    Ok(Listen {
+        rx,
+        unlisten: js_sys::Function::from(unlisten),
+    })
+}
+
+/// What to do with an incoming event when the buffer of a bounded stream is full.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Overflow {
+    /// Discard the oldest buffered event to make room for the incoming one.
+    #[default]
+    DropOldest,
+    /// Discard the incoming event.
+    DropNewest,
+    /// Replace the newest buffered event with the incoming one, so the stream always yields the latest value.
+    Coalesce,
+}
+
+/// Options for [`listen_with`].
+#[derive(Debug, Clone)]
+pub struct ListenOptions {
+    /// The maximum number of events buffered until read, values smaller than 1 are treated as 1.
+    pub capacity: usize,
+    /// What to do when the buffer is full.
+    pub overflow: Overflow,
+    /// The options passed to the underlying listener.
+    pub event_options: EventOptions,
+}
+
+impl ListenOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Default for ListenOptions {
+    fn default() -> Self {
+        Self {
+            capacity: 16,
+            overflow: Overflow::default(),
+            event_options: EventOptions::default(),
+        }
+    }
+}
+
+/// Listen to an event from the backend, buffering at most [`ListenOptions::capacity`] events.
+///
+/// Unlike [`listen`] the memory used by the returned stream is bounded, which makes it suitable for high-frequency events such as progress reports.
+/// Events arriving while the buffer is full are handled according to [`ListenOptions::overflow`].
+///
+/// The returned Future will automatically clean up it's underlying event listener when dropped, so no manual unlisten function needs to be called.
+///
+/// Events whose payload can't be deserialized into `T` are logged and skipped.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use futures::StreamExt;
+/// use tauri_sys::event::{listen_with, ListenOptions, Overflow};
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut progress = listen_with::<f64>(
+///     "download-progress",
+///     ListenOptions {
+///         capacity: 1,
+///         overflow: Overflow::Coalesce,
+///         ..Default::default()
+///     },
+/// )
+/// .await?;
+///
+/// while let Some(event) = progress.next().await {
+///     log::debug!("Downloaded {:.0}%", event.payload * 100.0);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn listen_with<T>(
+    event: &str,
+    options: ListenOptions,
+) -> crate::Result<impl Stream<Item = Event<T>>>
+where
+    T: DeserializeOwned + 'static,
+{
+    let buffer = Rc::new(RefCell::new(Buffer {
+        queue: VecDeque::new(),
+        capacity: options.capacity.max(1),
+        overflow: options.overflow,
+        waker: None,
+    }));
+
+    let tx = Rc::downgrade(&buffer);
+    let closure = Closure::<dyn FnMut(JsValue)>::new(move |raw| {
+        if let (Some(buffer), Some(event)) = (tx.upgrade(), deserialize_or_log(raw)) {
+            buffer.borrow_mut().push(event);
+        }
+    });
+    let unlisten = inner::listen(
+        event,
+        &closure,
+        serde_wasm_bindgen::to_value(&options.event_options)?,
+    )
+    .await?;
+    closure.forget();
+
+    Ok(BoundedListen {
+        buffer,
+        unlisten: js_sys::Function::from(unlisten),
+    })
+}
+
+struct Buffer<T> {
+    queue: VecDeque<T>,
+    capacity: usize,
+    overflow: Overflow,
+    waker: Option<Waker>,
+}
+
+impl<T> Buffer<T> {
+    fn push(&mut self, item: T) {
+        if self.queue.len() >= self.capacity {
+            match self.overflow {
+                Overflow::DropOldest => {
+                    self.queue.pop_front();
+                }
+                Overflow::DropNewest => return,
+                Overflow::Coalesce => {
+                    self.queue.pop_back();
+                }
+            }
+        }
+
+        self.queue.push_back(item);
+
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+struct BoundedListen<T> {
+    buffer: Rc<RefCell<Buffer<T>>>,
+    unlisten: js_sys::Function,
+}
+
+impl<T> Drop for BoundedListen<T> {
+    fn drop(&mut self) {
+        log::debug!("Calling unlisten for listen callback");
+        self.unlisten.call0(&wasm_bindgen::JsValue::NULL).unwrap();
+    }
+}
+
+impl<T> Stream for BoundedListen<T> {
+    type Item = T;
+
+    fn poll_next(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        let mut buffer = self.buffer.borrow_mut();
+
+        match buffer.queue.pop_front() {
+            Some(item) => std::task::Poll::Ready(Some(item)),
+            None => {
+                buffer.waker = Some(cx.waker().clone());
+                std::task::Poll::Pending
+            }
+        }
+    }
+}
+
+/// Deserializes a value received from JS, logging and discarding it when it doesn't match `T`.
+pub(crate) fn deserialize_or_log<T: DeserializeOwned>(raw: JsValue) -> Option<T> {
+    match serde_wasm_bindgen::from_value(raw) {
+        Ok(value) => Some(value),
+        Err(err) => {
+            log::error!(
+                "Failed to deserialize {}: {}",
+                std::any::type_name::<T>(),
+                err
+            );
+            None
+        }
+    }
+}
+
 pub(crate) struct Listen<T> {
     pub rx: mpsc::UnboundedReceiver<T>,
     pub unlisten: js_sys::Function,
@@ -143,6 +482,8 @@ impl<T> Stream for Listen<T> {
 /// The returned Future will automatically clean up it's underlying event listener when dropped, so no manual unlisten function needs to be called.
 /// See [Differences to the JavaScript API](../index.html#differences-to-the-javascript-api) for details.
 ///
+/// Resolves to an error if the payload of the event can't be deserialized into `T`.
+///
 /// # Example
 ///
 /// ```rust,no_run
@@ -168,24 +509,159 @@ pub async fn once<T>(event: &str, options: EventOptions) -> crate::Result<Event<
 where
     T: DeserializeOwned + 'static,
 {
-    let (tx, rx) = oneshot::channel::<Event<T>>();
+    let fut = listen_once(event, options).await?;
+
+    fut.await
+}
+
+/// Registers the listener for a one-off event, returning the future resolving to the event.
+async fn listen_once<T>(event: &str, options: EventOptions) -> crate::Result<Once<T>>
+where
+    T: DeserializeOwned + 'static,
+{
+    let (tx, rx) = oneshot::channel::<crate::Result<Event<T>>>();
 
     let closure: Closure<dyn FnMut(JsValue)> = Closure::once(move |raw| {
-        let _ = tx.send(serde_wasm_bindgen::from_value(raw).unwrap());
+        let _ = tx.send(serde_wasm_bindgen::from_value(raw).map_err(Into::into));
     });
     let unlisten = inner::once(event, &closure, serde_wasm_bindgen::to_value(&options)?).await?;
     closure.forget();
 
-    let fut = Once {
+    Ok(Once {
         rx,
         unlisten: js_sys::Function::from(unlisten),
-    };
+    })
+}
 
-    fut.await
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Request<'a, T> {
+    id: &'a str,
+    reply_to: &'a str,
+    payload: &'a T,
+}
+
+/// Sends a request to the backend over the event system and waits for the reply.
+///
+/// The request is emitted as `event` with a payload of the form `{ "id": string, "replyTo": string, "payload": Req }`.
+/// The backend answers by emitting the event named in `replyTo` with the response as its payload,
+/// the reply event name contains the unique correlation id of the request.
+///
+/// Resolves to [`Error::Timeout`](crate::Error::Timeout) if no reply arrived within `timeout`.
+/// The reply listener is removed once the reply arrived, the request timed out or the returned Future was dropped.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use serde::{Deserialize, Serialize};
+/// use std::time::Duration;
+/// use tauri_sys::event::request;
+///
+/// #[derive(Serialize)]
+/// struct Lookup {
+///     user_id: u32,
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct User {
+///     name: String,
+/// }
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let user = request::<_, User>("lookup-user", &Lookup { user_id: 1 }, Duration::from_secs(5)).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn request<Req, Resp>(
+    event: &str,
+    payload: &Req,
+    timeout: Duration,
+) -> crate::Result<Resp>
+where
+    Req: Serialize,
+    Resp: DeserializeOwned + 'static,
+{
+    let id = correlation_id();
+    let reply_to = format!("{}:reply:{}", event, id);
+
+    let reply = listen_once::<Resp>(&reply_to, EventOptions::new()).await?;
+
+    emit(
+        event,
+        &Request {
+            id: &id,
+            reply_to: &reply_to,
+            payload,
+        },
+        EventOptions::new(),
+    )
+    .await?;
+
+    match future::select(reply, Timer::new(timeout)).await {
+        future::Either::Left((event, _)) => Ok(event?.payload),
+        future::Either::Right(_) => Err(crate::Error::Timeout),
+    }
+}
+
+fn correlation_id() -> String {
+    thread_local! {
+        static NEXT_ID: std::cell::Cell<u32> = const { std::cell::Cell::new(0) };
+    }
+
+    let n = NEXT_ID.with(|next| {
+        let n = next.get();
+        next.set(n.wrapping_add(1));
+        n
+    });
+    let random = (js_sys::Math::random() * u32::MAX as f64) as u32;
+
+    format!("{:x}-{:08x}-{:x}", js_sys::Date::now() as u64, random, n)
+}
+
+/// A future resolving after the given duration, clearing the timeout when dropped.
+struct Timer {
+    rx: oneshot::Receiver<()>,
+    handle: JsValue,
+    _closure: Closure<dyn FnMut()>,
+}
+
+impl Timer {
+    fn new(duration: Duration) -> Self {
+        let (tx, rx) = oneshot::channel();
+
+        let closure: Closure<dyn FnMut()> = Closure::once(move || {
+            let _ = tx.send(());
+        });
+        let millis = duration.as_millis().min(i32::MAX as u128) as i32;
+        let handle = inner::setTimeout(&closure, millis);
+
+        Self {
+            rx,
+            handle,
+            _closure: closure,
+        }
+    }
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        inner::clearTimeout(&self.handle);
+    }
+}
+
+impl Future for Timer {
+    type Output = ();
+
+    fn poll(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Self::Output> {
+        self.rx.poll_unpin(cx).map(|_| ())
+    }
 }
 
 pub(crate) struct Once<T> {
-    pub rx: oneshot::Receiver<Event<T>>,
+    pub rx: oneshot::Receiver<crate::Result<Event<T>>>,
     pub unlisten: js_sys::Function,
 }
 
@@ -204,7 +680,9 @@ impl<T> Future for Once<T> {
         mut self: std::pin::Pin<&mut Self>,
         cx: &mut std::task::Context<'_>,
     ) -> std::task::Poll<Self::Output> {
-        self.rx.poll_unpin(cx).map_err(Into::into)
+        self.rx
+            .poll_unpin(cx)
+            .map(|res| res.map_err(Into::into).and_then(|event| event))
     }
 }
 
@@ -231,4 +709,10 @@ mod inner {
             options: JsValue,
         ) -> Result<JsValue, JsValue>;
     }
+
+    #[wasm_bindgen]
+    extern "C" {
+        pub fn setTimeout(handler: &Closure<dyn FnMut()>, timeout: i32) -> JsValue;
+        pub fn clearTimeout(handle: &JsValue);
+    }
 }
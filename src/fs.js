@@ -1,4 +1,28 @@
-const { invoke } = window.__TAURI__.core;
+const { invoke, transformCallback } = window.__TAURI__.core;
+
+class Channel {
+  id;
+  __TAURI_CHANNEL_MARKER__ = true;
+  #onmessage = () => {};
+
+  constructor() {
+    this.id = transformCallback((response) => {
+      this.#onmessage(response);
+    });
+  }
+
+  set onmessage(handler) {
+    this.#onmessage = handler;
+  }
+
+  get onmessage() {
+    return this.#onmessage;
+  }
+
+  toJSON() {
+    return `__CHANNEL__:${this.id}`;
+  }
+}
 
 async function readTextFile(filePath, options = {}) {
   return await invoke("plugin:fs|read_text_file", {
@@ -124,12 +148,65 @@ async function exists(path, options = {}) {
   return await invoke("plugin:fs|exists", { path, options });
 }
 
-async function metadata(path) {
+async function metadata(path, options = {}) {
   return await invoke("plugin:fs|metadata", {
     path,
+    options,
+  });
+}
+
+async function open(path, options = {}) {
+  return await invoke("plugin:fs|open", {
+    path,
+    options,
+  });
+}
+
+async function read(rid, len) {
+  const arr = await invoke("plugin:fs|read", {
+    rid,
+    len,
+  });
+
+  return Uint8Array.from(arr);
+}
+
+async function write(rid, data) {
+  return await invoke("plugin:fs|write", {
+    rid,
+    data: Array.from(data),
+  });
+}
+
+async function seek(rid, offset, whence) {
+  return await invoke("plugin:fs|seek", {
+    rid,
+    offset,
+    whence,
   });
 }
 
+async function close(rid) {
+  return await invoke("plugin:resources|close", {
+    rid,
+  });
+}
+
+async function watch(paths, options, handler) {
+  const onEvent = new Channel();
+  onEvent.onmessage = handler;
+
+  const rid = await invoke("plugin:fs|watch", {
+    paths,
+    options,
+    onEvent,
+  });
+
+  return () => {
+    void invoke("plugin:fs|unwatch", { rid });
+  };
+}
+
 export {
   readTextFile,
   readBinaryFile,
@@ -144,4 +221,10 @@ export {
   renameFile,
   exists,
   metadata,
+  open,
+  read,
+  write,
+  seek,
+  close,
+  watch,
 };
@@ -36,14 +36,26 @@
 //! Trying to execute any API with a URL not configured on the scope results in a promise rejection due to denied access.
 //!
 //! Note that this scope applies to **all** APIs on this module.
-use crate::Error;
+use crate::{event::Listen, Error};
+use camino::{Utf8Component, Utf8Path, Utf8PathBuf};
+use futures::{
+    channel::mpsc,
+    future::{FutureExt, LocalBoxFuture},
+    ready, stream, Sink, Stream, StreamExt,
+};
 use js_sys::{ArrayBuffer, Uint8Array};
-use serde::{Deserialize, Serialize};
+use serde::{de::IgnoredAny, Deserialize, Serialize};
 use serde_repr::*;
+use std::collections::HashSet;
+use std::mem;
 use std::path::{Path, PathBuf};
+use std::pin::Pin;
 use std::str;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use wasm_bindgen::{prelude::Closure, JsValue};
 
-#[derive(Serialize_repr, Clone, PartialEq, Eq, Debug)]
+#[derive(Serialize_repr, Deserialize_repr, Clone, Copy, PartialEq, Eq, Hash, Debug)]
 #[repr(u16)]
 pub enum BaseDirectory {
     Audio = 1,
@@ -89,6 +101,118 @@ struct FsOptions {
     pub dir: Option<BaseDirectory>,
 }
 
+/// A path relative to one of the [`BaseDirectory`]s.
+///
+/// The rules described in the [module documentation](self) are checked when the path is constructed:
+/// it must be valid UTF-8, must not be absolute and must not contain `..` components, so it never points outside of its base directory.
+/// `.` components are dropped, the empty path refers to the base directory itself.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tauri_sys::fs::{self, BaseDirectory, ScopedPath};
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let config = ScopedPath::new(BaseDirectory::AppConfig, "settings.json")?;
+/// let settings = fs::read_text_file(&config).await?;
+///
+/// assert!(ScopedPath::new(BaseDirectory::AppConfig, "../secrets").is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ScopedPath {
+    base: BaseDirectory,
+    rel: Utf8PathBuf,
+}
+
+impl ScopedPath {
+    /// Creates a path relative to `base`, failing if `rel` is not a valid relative path.
+    pub fn new(base: BaseDirectory, rel: impl AsRef<Path>) -> crate::Result<Self> {
+        let rel = rel.as_ref();
+        let Some(utf8) = Utf8Path::from_path(rel) else {
+            return Err(Error::Utf8(rel.to_path_buf()));
+        };
+
+        let mut normalized = Utf8PathBuf::new();
+        for component in utf8.components() {
+            match component {
+                Utf8Component::Normal(name) => normalized.push(name),
+                Utf8Component::CurDir => {}
+                Utf8Component::ParentDir | Utf8Component::RootDir | Utf8Component::Prefix(_) => {
+                    return Err(Error::InvalidScopedPath(rel.to_path_buf()))
+                }
+            }
+        }
+
+        Ok(Self {
+            base,
+            rel: normalized,
+        })
+    }
+
+    /// The base directory the path is relative to.
+    pub fn base(&self) -> BaseDirectory {
+        self.base
+    }
+
+    /// The path relative to the base directory.
+    pub fn rel(&self) -> &Utf8Path {
+        &self.rel
+    }
+
+    /// Creates a path with `rel` appended, failing if `rel` is not a valid relative path.
+    pub fn join(&self, rel: impl AsRef<Path>) -> crate::Result<Self> {
+        let rel = Self::new(self.base, rel)?.rel;
+
+        Ok(Self {
+            base: self.base,
+            rel: self.rel.join(rel),
+        })
+    }
+
+    /// The path without its final component, `None` if the path refers to the base directory itself.
+    pub fn parent(&self) -> Option<Self> {
+        self.rel.parent().map(|parent| Self {
+            base: self.base,
+            rel: parent.to_path_buf(),
+        })
+    }
+
+    /// The final component of the path, `None` if the path refers to the base directory itself.
+    pub fn file_name(&self) -> Option<&str> {
+        self.rel.file_name()
+    }
+
+    /// Resolves the path to an absolute path, see [`path::resolve_scoped`](crate::path::resolve_scoped).
+    #[cfg(feature = "path")]
+    pub async fn resolve(&self) -> crate::Result<PathBuf> {
+        crate::path::resolve_scoped(self).await
+    }
+
+    /// A unique path in the same directory, used for atomic writes.
+    fn temp_sibling(&self) -> crate::Result<Self> {
+        let name = format!(
+            ".{}.{:x}.tmp",
+            self.file_name().unwrap_or_default(),
+            (js_sys::Math::random() * u32::MAX as f64) as u32
+        );
+
+        match self.parent() {
+            Some(parent) => parent.join(name),
+            None => Self::new(self.base, name),
+        }
+    }
+
+    fn shared_base(&self, other: &Self) -> crate::Result<BaseDirectory> {
+        if self.base == other.base {
+            Ok(self.base)
+        } else {
+            Err(Error::BaseDirectoryMismatch(self.base, other.base))
+        }
+    }
+}
+
 #[derive(Serialize, Clone, PartialEq, Debug)]
 struct FsTextFileOption {
     pub contents: String,
@@ -152,20 +276,14 @@ pub struct Metadata {
 /// ```rust,no_run
 /// use tauri_sys::fs;
 ///
-/// fs::copy_file(source, destination, BaseDirectory::Download).expect("could not copy file");
+/// fs::copy_file(&source, &destination).expect("could not copy file");
 /// ```
-pub async fn copy_file(source: &Path, destination: &Path, dir: BaseDirectory) -> crate::Result<()> {
-    let Some(source) = source.to_str() else {
-        return Err(Error::Utf8(source.to_path_buf()));
-    };
-
-    let Some(destination) = destination.to_str() else {
-        return Err(Error::Utf8(destination.to_path_buf()));
-    };
+pub async fn copy_file(source: &ScopedPath, destination: &ScopedPath) -> crate::Result<()> {
+    let dir = source.shared_base(destination)?;
 
     let raw = inner::copyFile(
-        source,
-        destination,
+        source.rel.as_str(),
+        destination.rel.as_str(),
         serde_wasm_bindgen::to_value(&FsOptions { dir: Some(dir) })?,
     )
     .await?;
@@ -181,19 +299,15 @@ pub async fn copy_file(source: &Path, destination: &Path, dir: BaseDirectory) ->
 /// ```rust,no_run
 /// use tauri_sys::fs;
 ///
-/// fs::create_dir(dir, BaseDirectory::Download).expect("could not create directory");
+/// fs::create_dir(&dir).expect("could not create directory");
 /// ```
-pub async fn create_dir(dir: &Path, base_dir: BaseDirectory) -> crate::Result<()> {
+pub async fn create_dir(dir: &ScopedPath) -> crate::Result<()> {
     let recursive = Some(false);
 
-    let Some(dir) = dir.to_str() else {
-        return Err(Error::Utf8(dir.to_path_buf()));
-    };
-
     Ok(inner::createDir(
-        dir,
+        dir.rel.as_str(),
         serde_wasm_bindgen::to_value(&FsDirOptions {
-            dir: Some(base_dir),
+            dir: Some(dir.base),
             recursive,
         })?,
     )
@@ -207,19 +321,15 @@ pub async fn create_dir(dir: &Path, base_dir: BaseDirectory) -> crate::Result<()
 /// ```rust,no_run
 /// use tauri_sys::fs;
 ///
-/// fs::create_dir_all(dir, BaseDirectory::Download).expect("could not create directory");
+/// fs::create_dir_all(&dir).expect("could not create directory");
 /// ```
-pub async fn create_dir_all(dir: &Path, base_dir: BaseDirectory) -> crate::Result<()> {
+pub async fn create_dir_all(dir: &ScopedPath) -> crate::Result<()> {
     let recursive = Some(true);
 
-    let Some(dir) = dir.to_str() else {
-        return Err(Error::Utf8(dir.to_path_buf()));
-    };
-
     Ok(inner::createDir(
-        dir,
+        dir.rel.as_str(),
         serde_wasm_bindgen::to_value(&FsDirOptions {
-            dir: Some(base_dir),
+            dir: Some(dir.base),
             recursive,
         })?,
     )
@@ -233,16 +343,14 @@ pub async fn create_dir_all(dir: &Path, base_dir: BaseDirectory) -> crate::Resul
 /// ```rust,no_run
 /// use tauri_sys::fs;
 ///
-/// let file_exists = fs::exists(path, BaseDirectory::Download).expect("could not check if path exists");
+/// let file_exists = fs::exists(&path).expect("could not check if path exists");
 /// ```
-pub async fn exists(path: &Path, dir: BaseDirectory) -> crate::Result<bool> {
-    let Some(path) = path.to_str() else {
-        return Err(Error::Utf8(path.to_path_buf()));
-    };
-
+pub async fn exists(path: &ScopedPath) -> crate::Result<bool> {
     let raw = inner::exists(
-        path,
-        serde_wasm_bindgen::to_value(&FsOptions { dir: Some(dir) })?,
+        path.rel.as_str(),
+        serde_wasm_bindgen::to_value(&FsOptions {
+            dir: Some(path.base),
+        })?,
     )
     .await?;
 
@@ -256,16 +364,14 @@ pub async fn exists(path: &Path, dir: BaseDirectory) -> crate::Result<bool> {
 /// ```rust,no_run
 /// use tauri_sys::fs;
 ///
-/// let contents = fs::read_binary_file(filePath, BaseDirectory::Download).expect("could not read file contents");
+/// let contents = fs::read_binary_file(&path).expect("could not read file contents");
 /// ```
-pub async fn read_binary_file(path: &Path, dir: BaseDirectory) -> crate::Result<Vec<u8>> {
-    let Some(path) = path.to_str() else {
-        return Err(Error::Utf8(path.to_path_buf()));
-    };
-
+pub async fn read_binary_file(path: &ScopedPath) -> crate::Result<Vec<u8>> {
     let raw = inner::readBinaryFile(
-        path,
-        serde_wasm_bindgen::to_value(&FsOptions { dir: Some(dir) })?,
+        path.rel.as_str(),
+        serde_wasm_bindgen::to_value(&FsOptions {
+            dir: Some(path.base),
+        })?,
     )
     .await?;
 
@@ -279,18 +385,15 @@ pub async fn read_binary_file(path: &Path, dir: BaseDirectory) -> crate::Result<
 /// ```rust,no_run
 /// use tauri_sys::fs;
 ///
-/// let files = fs::read_dir(path, BaseDirectory::Download).expect("could not read directory");
+/// let files = fs::read_dir(&path).expect("could not read directory");
 /// ```
-pub async fn read_dir(path: &Path, dir: BaseDirectory) -> crate::Result<Vec<FileEntry>> {
+pub async fn read_dir(path: &ScopedPath) -> crate::Result<Vec<FileEntry>> {
     let recursive = Some(false);
-    let Some(path) = path.to_str() else {
-        return Err(Error::Utf8(path.to_path_buf()));
-    };
 
     let raw = inner::readDir(
-        path,
+        path.rel.as_str(),
         serde_wasm_bindgen::to_value(&FsDirOptions {
-            dir: Some(dir),
+            dir: Some(path.base),
             recursive,
         })?,
     )
@@ -301,23 +404,22 @@ pub async fn read_dir(path: &Path, dir: BaseDirectory) -> crate::Result<Vec<File
 
 /// List directory files recursively.
 ///
+/// The whole tree is read at once, use [`walk`] for large directories or to filter the entries.
+///
 /// # Example
 ///
 /// ```rust,no_run
 /// use tauri_sys::fs;
 ///
-/// let files = fs::read_dir_all(path, BaseDirectory::Download).expect("could not read directory");
+/// let files = fs::read_dir_all(&path).expect("could not read directory");
 /// ```
-pub async fn read_dir_all(path: &Path, dir: BaseDirectory) -> crate::Result<Vec<FileEntry>> {
+pub async fn read_dir_all(path: &ScopedPath) -> crate::Result<Vec<FileEntry>> {
     let recursive = Some(true);
-    let Some(path) = path.to_str() else {
-        return Err(Error::Utf8(path.to_path_buf()));
-    };
 
     let raw = inner::readDir(
-        path,
+        path.rel.as_str(),
         serde_wasm_bindgen::to_value(&FsDirOptions {
-            dir: Some(dir),
+            dir: Some(path.base),
             recursive,
         })?,
     )
@@ -333,16 +435,14 @@ pub async fn read_dir_all(path: &Path, dir: BaseDirectory) -> crate::Result<Vec<
 /// ```rust,no_run
 /// use tauri_sys::fs;
 ///
-/// let contents = fs::readTextFile(path, BaseDirectory::Download).expect("could not read file as text");
+/// let contents = fs::read_text_file(&path).expect("could not read file as text");
 /// ```
-pub async fn read_text_file(path: &Path, dir: BaseDirectory) -> crate::Result<String> {
-    let Some(path) = path.to_str() else {
-        return Err(Error::Utf8(path.to_path_buf()));
-    };
-
+pub async fn read_text_file(path: &ScopedPath) -> crate::Result<String> {
     let raw = inner::readTextFile(
-        path,
-        serde_wasm_bindgen::to_value(&FsOptions { dir: Some(dir) })?,
+        path.rel.as_str(),
+        serde_wasm_bindgen::to_value(&FsOptions {
+            dir: Some(path.base),
+        })?,
     )
     .await?;
 
@@ -357,18 +457,15 @@ pub async fn read_text_file(path: &Path, dir: BaseDirectory) -> crate::Result<St
 /// ```rust,no_run
 /// use tauri_sys::fs;
 ///
-/// fs::remove_dir(path, BaseDirectory::Download).expect("could not remove directory");
+/// fs::remove_dir(&path).expect("could not remove directory");
 /// ```
-pub async fn remove_dir(dir: &Path, base_dir: BaseDirectory) -> crate::Result<()> {
+pub async fn remove_dir(dir: &ScopedPath) -> crate::Result<()> {
     let recursive = Some(false);
-    let Some(dir) = dir.to_str() else {
-        return Err(Error::Utf8(dir.to_path_buf()));
-    };
 
     Ok(inner::removeDir(
-        dir,
+        dir.rel.as_str(),
         serde_wasm_bindgen::to_value(&FsDirOptions {
-            dir: Some(base_dir),
+            dir: Some(dir.base),
             recursive,
         })?,
     )
@@ -382,18 +479,15 @@ pub async fn remove_dir(dir: &Path, base_dir: BaseDirectory) -> crate::Result<()
 /// ```rust,no_run
 /// use tauri_sys::fs;
 ///
-/// fs::remove_dir_all(path, BaseDirectory::Download).expect("could not remove directory");
+/// fs::remove_dir_all(&path).expect("could not remove directory");
 /// ```
-pub async fn remove_dir_all(dir: &Path, base_dir: BaseDirectory) -> crate::Result<()> {
+pub async fn remove_dir_all(dir: &ScopedPath) -> crate::Result<()> {
     let recursive = Some(true);
-    let Some(dir) = dir.to_str() else {
-        return Err(Error::Utf8(dir.to_path_buf()));
-    };
 
     Ok(inner::removeDir(
-        dir,
+        dir.rel.as_str(),
         serde_wasm_bindgen::to_value(&FsDirOptions {
-            dir: Some(base_dir),
+            dir: Some(dir.base),
             recursive,
         })?,
     )
@@ -407,45 +501,35 @@ pub async fn remove_dir_all(dir: &Path, base_dir: BaseDirectory) -> crate::Resul
 /// ```rust,no_run
 /// use tauri_sys::fs;
 ///
-/// fs::remove_file(path, BaseDirectory::Download).expect("could not remove file");
+/// fs::remove_file(&path).expect("could not remove file");
 /// ```
-pub async fn remove_file(file: &Path, dir: BaseDirectory) -> crate::Result<()> {
-    let Some(file) = file.to_str() else {
-        return Err(Error::Utf8(file.to_path_buf()));
-    };
-
+pub async fn remove_file(file: &ScopedPath) -> crate::Result<()> {
     Ok(inner::removeFile(
-        file,
-        serde_wasm_bindgen::to_value(&FsOptions { dir: Some(dir) })?,
+        file.rel.as_str(),
+        serde_wasm_bindgen::to_value(&FsOptions {
+            dir: Some(file.base),
+        })?,
     )
     .await?)
 }
 
 /// Renames a file.
 ///
+/// Both paths must share the same base directory.
+///
 /// # Example
 ///
 /// ```rust,no_run
 /// use tauri_sys::fs;
 ///
-/// fs::rename_file(old_path, new_path, BaseDirectory::Download).expect("could not rename file");
+/// fs::rename_file(&old_path, &new_path).expect("could not rename file");
 /// ```
-pub async fn rename_file(
-    old_path: &Path,
-    new_path: &Path,
-    dir: BaseDirectory,
-) -> crate::Result<()> {
-    let Some(old_path) = old_path.to_str() else {
-        return Err(Error::Utf8(old_path.to_path_buf()));
-    };
-
-    let Some(new_path) = new_path.to_str() else {
-        return Err(Error::Utf8(new_path.to_path_buf()));
-    };
+pub async fn rename_file(old_path: &ScopedPath, new_path: &ScopedPath) -> crate::Result<()> {
+    let dir = old_path.shared_base(new_path)?;
 
     Ok(inner::renameFile(
-        old_path,
-        new_path,
+        old_path.rel.as_str(),
+        new_path.rel.as_str(),
         serde_wasm_bindgen::to_value(&FsOptions { dir: Some(dir) })?,
     )
     .await?)
@@ -453,50 +537,46 @@ pub async fn rename_file(
 
 /// Writes a byte array content to a file.
 ///
+/// Existing files are overwritten, see [`OpenOptions`] for appending or atomic writes.
+///
 /// # Example
 ///
 /// ```rust,no_run
 /// use tauri_sys::fs;
 ///
-/// fs::write_binary_file(path, contents, BaseDirectory::Download).expect("could not writet binary file");
+/// fs::write_binary_file(&path, contents).expect("could not writet binary file");
 /// ```
-pub async fn write_binary_file(
-    path: &Path,
-    contents: &Vec<u8>,
-    dir: BaseDirectory,
-) -> crate::Result<()> {
-    let Some(path) = path.to_str() else {
-        return Err(Error::Utf8(path.to_path_buf()));
-    };
-
+pub async fn write_binary_file(path: &ScopedPath, contents: &Vec<u8>) -> crate::Result<()> {
     let array = Uint8Array::from(contents.as_slice());
 
     Ok(inner::writeBinaryFile(
-        path,
+        path.rel.as_str(),
         array.buffer(),
-        serde_wasm_bindgen::to_value(&FsOptions { dir: Some(dir) })?,
+        serde_wasm_bindgen::to_value(&FsOptions {
+            dir: Some(path.base),
+        })?,
     )
     .await?)
 }
 
 /// Writes a UTF-8 text file.
 ///
+/// Existing files are overwritten, see [`OpenOptions`] for appending or atomic writes.
+///
 /// # Example
 ///
 /// ```rust,no_run
 /// use tauri_sys::fs;
 ///
-/// fs::write_text_file(path, contents, BaseDirectory::Download).expect("could not writet binary file");
+/// fs::write_text_file(&path, contents).expect("could not writet binary file");
 /// ```
-pub async fn write_text_file(path: &Path, contents: &str, dir: BaseDirectory) -> crate::Result<()> {
-    let Some(path) = path.to_str() else {
-        return Err(Error::Utf8(path.to_path_buf()));
-    };
-
+pub async fn write_text_file(path: &ScopedPath, contents: &str) -> crate::Result<()> {
     Ok(inner::writeTextFile(
-        path,
+        path.rel.as_str(),
         &contents,
-        serde_wasm_bindgen::to_value(&FsOptions { dir: Some(dir) })?,
+        serde_wasm_bindgen::to_value(&FsOptions {
+            dir: Some(path.base),
+        })?,
     )
     .await?)
 }
@@ -508,21 +588,1413 @@ pub async fn write_text_file(path: &Path, contents: &str, dir: BaseDirectory) ->
 /// ```rust,no_run
 /// use tauri_sys::fs;
 ///
-/// fs::metadata(path).expect("failed to get metadata");
+/// fs::metadata(&path).expect("failed to get metadata");
+/// ```
+pub async fn metadata(path: &ScopedPath) -> crate::Result<Metadata> {
+    let raw = inner::metadata(
+        path.rel.as_str(),
+        serde_wasm_bindgen::to_value(&FsOptions {
+            dir: Some(path.base),
+        })?,
+    )
+    .await?;
+
+    Ok(serde_wasm_bindgen::from_value(raw)?)
+}
+
+#[derive(Serialize, Clone, PartialEq, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+struct OpenFlags {
+    dir: Option<BaseDirectory>,
+    read: bool,
+    write: bool,
+    append: bool,
+    create: bool,
+    create_new: bool,
+    truncate: bool,
+}
+
+/// A file opened on the backend, closed when dropped.
+#[derive(Debug)]
+struct FileHandle {
+    rid: u32,
+}
+
+impl FileHandle {
+    async fn open(path: &ScopedPath, flags: OpenFlags) -> crate::Result<Self> {
+        let flags = OpenFlags {
+            dir: Some(path.base),
+            ..flags
+        };
+        let raw = inner::open(path.rel.as_str(), serde_wasm_bindgen::to_value(&flags)?).await?;
+
+        Ok(Self {
+            rid: serde_wasm_bindgen::from_value(raw)?,
+        })
+    }
+
+    async fn seek(&self, offset: u64) -> crate::Result<()> {
+        inner::seek(self.rid, offset as f64, 0).await?;
+
+        Ok(())
+    }
+
+    async fn read(&self, len: usize) -> crate::Result<Vec<u8>> {
+        let raw = inner::read(self.rid, len as u32).await?;
+
+        Ok(Uint8Array::new(&raw).to_vec())
+    }
+
+    /// Writes the whole buffer, the backend reports how many bytes each call wrote.
+    async fn write(&self, mut data: &[u8]) -> crate::Result<()> {
+        while !data.is_empty() {
+            let raw = inner::write(self.rid, Uint8Array::from(data)).await?;
+            let written: usize = serde_wasm_bindgen::from_value(raw)?;
+
+            if written == 0 {
+                return Err(Error::WriteZero(data.len()));
+            }
+
+            data = &data[written.min(data.len())..];
+        }
+
+        Ok(())
+    }
+
+    /// Closes the file and waits for the backend to release it, dropping the handle closes it in the background.
+    async fn close(self) -> crate::Result<()> {
+        let rid = self.rid;
+        // already closed here, skip the close on drop
+        std::mem::forget(self);
+
+        inner::close(rid).await?;
+
+        Ok(())
+    }
+}
+
+impl Drop for FileHandle {
+    fn drop(&mut self) {
+        let rid = self.rid;
+        wasm_bindgen_futures::spawn_local(async move {
+            if let Err(e) = inner::close(rid).await {
+                log::error!("Failed to close file: {:?}", e);
+            }
+        });
+    }
+}
+
+/// Options for [`read_stream`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadStreamOptions {
+    /// The position in the file to start reading from.
+    pub offset: u64,
+    /// The maximum number of bytes to read, `None` to read until the end of the file.
+    pub length: Option<u64>,
+    /// The maximum size of the chunks yielded by the stream.
+    pub chunk_size: usize,
+}
+
+impl Default for ReadStreamOptions {
+    fn default() -> Self {
+        Self {
+            offset: 0,
+            length: None,
+            chunk_size: 64 * 1024,
+        }
+    }
+}
+
+/// Reads a file in chunks.
+///
+/// Unlike [`read_binary_file`] the file is never held in memory as a whole, which makes this suitable for large files.
+/// The stream ends at the end of the file or after [`ReadStreamOptions::length`] bytes were read, and after the first error.
+/// The file is closed once the stream ends, or in the background when the stream is dropped before.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use futures::StreamExt;
+/// use tauri_sys::fs::{self, BaseDirectory, ReadStreamOptions, ScopedPath};
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut chunks = fs::read_stream(
+///     &ScopedPath::new(BaseDirectory::Video, "video.mp4")?,
+///     ReadStreamOptions {
+///         offset: 1024,
+///         ..Default::default()
+///     },
+/// )
+/// .await?;
+///
+/// while let Some(chunk) = chunks.next().await {
+///     let chunk = chunk?;
+///     log::debug!("Read {} bytes", chunk.len());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn read_stream(
+    path: &ScopedPath,
+    options: ReadStreamOptions,
+) -> crate::Result<impl Stream<Item = crate::Result<Vec<u8>>>> {
+    let file = FileHandle::open(
+        path,
+        OpenFlags {
+            read: true,
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    if options.offset > 0 {
+        file.seek(options.offset).await?;
+    }
+
+    let chunk_size = options.chunk_size.max(1);
+
+    Ok(stream::try_unfold(
+        (file, options.length),
+        move |(file, remaining)| async move {
+            let len = match remaining {
+                Some(0) => {
+                    file.close().await?;
+                    return Ok(None);
+                }
+                Some(remaining) => remaining.min(chunk_size as u64) as usize,
+                None => chunk_size,
+            };
+
+            let chunk = file.read(len).await?;
+            if chunk.is_empty() {
+                file.close().await?;
+                return Ok(None);
+            }
+
+            let remaining = remaining.map(|remaining| remaining - chunk.len() as u64);
+
+            Ok(Some((chunk, (file, remaining))))
+        },
+    ))
+}
+
+/// Options for how [`OpenOptions::write`] writes to a file, mirroring [`std::fs::OpenOptions`].
+///
+/// By default a file is created if it doesn't exist and truncated if it does, just like [`write_binary_file`] does.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tauri_sys::fs::{BaseDirectory, OpenOptions, ScopedPath};
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let journal = ScopedPath::new(BaseDirectory::AppLog, "journal.log")?;
+/// OpenOptions::new()
+///     .set_append(true)
+///     .write(&journal, b"started\n")
+///     .await?;
+///
+/// let config = ScopedPath::new(BaseDirectory::AppConfig, "config.json")?;
+/// OpenOptions::new()
+///     .set_atomic(true)
+///     .write(&config, br#"{ "theme": "dark" }"#)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OpenOptions {
+    append: bool,
+    create_new: bool,
+    truncate: bool,
+    atomic: bool,
+}
+
+impl Default for OpenOptions {
+    fn default() -> Self {
+        Self {
+            append: false,
+            create_new: false,
+            truncate: true,
+            atomic: false,
+        }
+    }
+}
+
+impl OpenOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends to the end of the file instead of overwriting it, the file is still created if it doesn't exist.
+    pub fn set_append(&mut self, append: bool) -> &mut Self {
+        self.append = append;
+        self
+    }
+
+    /// Fails with the error reported by the backend if the file already exists.
+    pub fn set_create_new(&mut self, create_new: bool) -> &mut Self {
+        self.create_new = create_new;
+        self
+    }
+
+    /// Truncates an existing file before writing to it.
+    ///
+    /// Without truncation, the contents overwrite the start of the file and the rest of it is left in place.
+    /// Ignored when appending.
+    pub fn set_truncate(&mut self, truncate: bool) -> &mut Self {
+        self.truncate = truncate;
+        self
+    }
+
+    /// Writes the contents to a temporary file next to the target and renames it over the target once complete,
+    /// so the target never holds partially written contents.
+    ///
+    /// Atomic writes always replace the whole file: when appending, the existing contents are read and written again.
+    /// The temporary file may be left behind if the app exits before the rename.
+    pub fn set_atomic(&mut self, atomic: bool) -> &mut Self {
+        self.atomic = atomic;
+        self
+    }
+
+    /// Writes `contents` to the file at `path` according to these options.
+    pub async fn write(&self, path: &ScopedPath, contents: &[u8]) -> crate::Result<()> {
+        if self.atomic {
+            return self.write_atomic(path, contents).await;
+        }
+
+        let file = FileHandle::open(
+            path,
+            OpenFlags {
+                write: true,
+                append: self.append,
+                create: true,
+                create_new: self.create_new,
+                truncate: self.truncate && !self.append,
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        file.write(contents).await?;
+        file.close().await
+    }
+
+    async fn write_atomic(&self, path: &ScopedPath, contents: &[u8]) -> crate::Result<()> {
+        if !self.create_new {
+            return self.replace(path, contents).await;
+        }
+
+        // let the backend decide whether the file exists, reserving the path while the contents are written
+        let reserved = FileHandle::open(
+            path,
+            OpenFlags {
+                write: true,
+                create_new: true,
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        let written = match reserved.close().await {
+            Ok(()) => self.replace(path, contents).await,
+            Err(e) => Err(e),
+        };
+
+        if written.is_err() {
+            if let Err(e) = remove_file(path).await {
+                log::warn!("Failed to remove reserved file {:?}: {}", path.rel, e);
+            }
+        }
+
+        written
+    }
+
+    /// Writes the contents to a temporary file and renames it over `path`.
+    async fn replace(&self, path: &ScopedPath, contents: &[u8]) -> crate::Result<()> {
+        let mut buffer;
+        let mut contents = contents;
+        if self.append && exists(path).await? {
+            buffer = read_binary_file(path).await?;
+            buffer.extend_from_slice(contents);
+            contents = &buffer;
+        }
+
+        let temp = path.temp_sibling()?;
+        let written = async {
+            let file = FileHandle::open(
+                &temp,
+                OpenFlags {
+                    write: true,
+                    create_new: true,
+                    ..Default::default()
+                },
+            )
+            .await?;
+            let written = file.write(contents).await;
+            // the file can neither be renamed nor removed while open on some platforms
+            file.close().await?;
+            written?;
+
+            rename_file(&temp, path).await
+        }
+        .await;
+
+        if written.is_err() {
+            if let Err(e) = remove_file(&temp).await {
+                log::warn!("Failed to remove temporary file {:?}: {}", temp.rel, e);
+            }
+        }
+
+        written
+    }
+}
+
+/// Writes a file in chunks.
+///
+/// Every chunk passed to [`FileWriter::write`] is appended to the file, so large files never need to be held in memory as a whole.
+/// Call [`FileWriter::close`] to wait for the file to be closed, dropping the writer closes it in the background.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tauri_sys::fs::{BaseDirectory, FileWriter, ScopedPath};
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let writer = FileWriter::create(&ScopedPath::new(BaseDirectory::Download, "export.csv")?).await?;
+///
+/// writer.write(b"id,name\n").await?;
+/// writer.write(b"1,tauri\n").await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct FileWriter {
+    file: FileHandle,
+}
+
+impl FileWriter {
+    /// Creates a file, truncating it if it already exists.
+    pub async fn create(path: &ScopedPath) -> crate::Result<Self> {
+        let file = FileHandle::open(
+            path,
+            OpenFlags {
+                write: true,
+                create: true,
+                truncate: true,
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        Ok(Self { file })
+    }
+
+    /// Opens a file to append to, creating it if it doesn't exist.
+    pub async fn append(path: &ScopedPath) -> crate::Result<Self> {
+        let file = FileHandle::open(
+            path,
+            OpenFlags {
+                write: true,
+                append: true,
+                create: true,
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        Ok(Self { file })
+    }
+
+    /// Appends a chunk to the file.
+    pub async fn write(&self, chunk: &[u8]) -> crate::Result<()> {
+        self.file.write(chunk).await
+    }
+
+    /// Closes the file and waits for the backend to release it.
+    pub async fn close(self) -> crate::Result<()> {
+        self.file.close().await
+    }
+
+    /// Converts the writer into a [`Sink`] of chunks, closing the sink closes the file.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use futures::{stream, SinkExt, StreamExt};
+    /// use tauri_sys::fs::{self, BaseDirectory, FileWriter, ReadStreamOptions, ScopedPath};
+    ///
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let chunks = fs::read_stream(&ScopedPath::new(BaseDirectory::Download, "in.bin")?, ReadStreamOptions::default()).await?;
+    /// let writer = FileWriter::create(&ScopedPath::new(BaseDirectory::Download, "out.bin")?).await?;
+    ///
+    /// chunks.forward(writer.into_sink()).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn into_sink(self) -> impl Sink<Vec<u8>, Error = crate::Error> {
+        FileSink::Idle(self)
+    }
+}
+
+/// The sink returned by [`FileWriter::into_sink`].
+enum FileSink {
+    Idle(FileWriter),
+    Writing(LocalBoxFuture<'static, crate::Result<FileWriter>>),
+    Closing(LocalBoxFuture<'static, crate::Result<()>>),
+    Closed,
+}
+
+impl FileSink {
+    /// Waits for the pending write, if any.
+    fn poll_written(&mut self, cx: &mut Context<'_>) -> Poll<crate::Result<()>> {
+        if let Self::Writing(write) = self {
+            match ready!(write.as_mut().poll(cx)) {
+                Ok(writer) => *self = Self::Idle(writer),
+                Err(e) => {
+                    *self = Self::Closed;
+                    return Poll::Ready(Err(e));
+                }
+            }
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl Sink<Vec<u8>> for FileSink {
+    type Error = crate::Error;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<crate::Result<()>> {
+        self.get_mut().poll_written(cx)
+    }
+
+    fn start_send(self: Pin<&mut Self>, chunk: Vec<u8>) -> crate::Result<()> {
+        let this = self.get_mut();
+
+        match mem::replace(this, Self::Closed) {
+            Self::Idle(writer) => {
+                *this = Self::Writing(
+                    async move {
+                        writer.write(&chunk).await?;
+
+                        Ok(writer)
+                    }
+                    .boxed_local(),
+                );
+
+                Ok(())
+            }
+            Self::Writing(_) => panic!("`start_send` called before `poll_ready` completed"),
+            Self::Closing(_) | Self::Closed => Err(Error::Binding("file is closed".to_string())),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<crate::Result<()>> {
+        self.get_mut().poll_written(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<crate::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_written(cx))?;
+
+        if let Self::Idle(_) = this {
+            if let Self::Idle(writer) = mem::replace(this, Self::Closed) {
+                *this = Self::Closing(writer.close().boxed_local());
+            }
+        }
+
+        if let Self::Closing(close) = this {
+            let closed = ready!(close.as_mut().poll(cx));
+            *this = Self::Closed;
+
+            return Poll::Ready(closed);
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// A change to the file system reported by [`watch`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WatchEvent {
+    /// A file or directory was created.
+    Created(PathBuf),
+    /// The contents or metadata of a file or directory changed.
+    Modified(PathBuf),
+    /// A file or directory was removed.
+    Removed(PathBuf),
+    /// A file or directory was renamed.
+    Renamed { from: PathBuf, to: PathBuf },
+}
+
+/// Options for [`watch`].
+#[derive(Debug, Clone, Default)]
+pub struct WatchOptions {
+    /// Whether to watch the contents of subdirectories as well.
+    pub recursive: bool,
+    /// How long to wait for further changes before reporting them, `None` to report changes immediately.
+    pub debounce: Option<Duration>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WatchFlags {
+    dir: BaseDirectory,
+    recursive: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    delay_ms: Option<u64>,
+}
+
+/// The events emitted by the backend, mirroring the events of the `notify` crate.
+#[derive(Deserialize)]
+struct RawWatchEvent {
+    #[serde(rename = "type")]
+    kind: RawWatchEventKind,
+    paths: Vec<PathBuf>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum RawWatchEventKind {
+    Any,
+    Other,
+    Access(IgnoredAny),
+    Create(IgnoredAny),
+    Modify(RawModifyKind),
+    Remove(IgnoredAny),
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+enum RawModifyKind {
+    Rename {
+        #[serde(default)]
+        mode: Option<String>,
+    },
+    #[serde(other)]
+    Other,
+}
+
+impl RawWatchEvent {
+    fn into_events(self) -> Vec<WatchEvent> {
+        match self.kind {
+            RawWatchEventKind::Create(_) => {
+                self.paths.into_iter().map(WatchEvent::Created).collect()
+            }
+            RawWatchEventKind::Remove(_) => {
+                self.paths.into_iter().map(WatchEvent::Removed).collect()
+            }
+            RawWatchEventKind::Modify(RawModifyKind::Rename { mode }) => {
+                match (mode.as_deref(), self.paths.as_slice()) {
+                    (Some("both"), [from, to]) => vec![WatchEvent::Renamed {
+                        from: from.clone(),
+                        to: to.clone(),
+                    }],
+                    (Some("from"), _) => self.paths.into_iter().map(WatchEvent::Removed).collect(),
+                    (Some("to"), _) => self.paths.into_iter().map(WatchEvent::Created).collect(),
+                    _ => self.paths.into_iter().map(WatchEvent::Modified).collect(),
+                }
+            }
+            RawWatchEventKind::Modify(RawModifyKind::Other) => {
+                self.paths.into_iter().map(WatchEvent::Modified).collect()
+            }
+            RawWatchEventKind::Any | RawWatchEventKind::Other | RawWatchEventKind::Access(_) => {
+                Vec::new()
+            }
+        }
+    }
+}
+
+/// Watches a file or directory for changes.
+///
+/// The returned Stream will automatically stop watching when dropped, so no manual unwatch function needs to be called.
+///
+/// Renames reported as separate events for the old and the new path are yielded as [`WatchEvent::Removed`] and [`WatchEvent::Created`] respectively.
+/// Events that don't change the file system, such as a file being read, are skipped.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use futures::StreamExt;
+/// use std::time::Duration;
+/// use tauri_sys::fs::{self, BaseDirectory, ScopedPath, WatchEvent, WatchOptions};
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut events = fs::watch(
+///     &ScopedPath::new(BaseDirectory::Document, "notes")?,
+///     WatchOptions {
+///         recursive: true,
+///         debounce: Some(Duration::from_millis(500)),
+///     },
+/// )
+/// .await?;
+///
+/// while let Some(event) = events.next().await {
+///     if let WatchEvent::Modified(path) = event {
+///         log::debug!("{} changed", path.display());
+///     }
+/// }
+/// # Ok(())
+/// # }
 /// ```
-pub async fn metadata(path: &Path) -> crate::Result<Metadata> {
-    let Some(path) = path.to_str() else {
-        return Err(Error::Utf8(path.to_path_buf()));
+pub async fn watch(
+    path: &ScopedPath,
+    options: WatchOptions,
+) -> crate::Result<impl Stream<Item = WatchEvent>> {
+    let flags = WatchFlags {
+        dir: path.base,
+        recursive: options.recursive,
+        delay_ms: options.debounce.map(|debounce| debounce.as_millis() as u64),
     };
 
-    let raw = inner::metadata(path).await?;
+    let (tx, rx) = mpsc::unbounded::<WatchEvent>();
 
-    Ok(serde_wasm_bindgen::from_value(raw)?)
+    let closure =
+        Closure::<dyn FnMut(JsValue)>::new(move |raw| {
+            match serde_wasm_bindgen::from_value::<RawWatchEvent>(raw) {
+                Ok(event) => {
+                    for event in event.into_events() {
+                        let _ = tx.unbounded_send(event);
+                    }
+                }
+                Err(err) => log::error!("Failed to deserialize watch event: {}", err),
+            }
+        });
+    let unwatch = inner::watch(
+        serde_wasm_bindgen::to_value(&[path.rel.as_str()])?,
+        serde_wasm_bindgen::to_value(&flags)?,
+        &closure,
+    )
+    .await?;
+    closure.forget();
+
+    Ok(Listen {
+        rx,
+        unlisten: js_sys::Function::from(unwatch),
+    })
+}
+
+/// Options for [`walk`].
+///
+/// Glob patterns are matched against the path of an entry relative to the walked directory, using `/` as the separator.
+/// `*` doesn't match across directories while `**` does, so `**/*.rs` matches every Rust file below the walked directory.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tauri_sys::fs::WalkOptions;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut options = WalkOptions::new();
+/// options
+///     .set_max_depth(Some(3))
+///     .add_include("**/*.md")?
+///     .add_exclude("**/node_modules")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WalkOptions {
+    max_depth: Option<usize>,
+    include: Vec<glob::Pattern>,
+    exclude: Vec<glob::Pattern>,
+    follow_symlinks: bool,
+    metadata: bool,
+}
+
+impl WalkOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits how deep the walk descends, `Some(1)` only yields the direct children of the walked directory.
+    pub fn set_max_depth(&mut self, max_depth: Option<usize>) -> &mut Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Only yields entries matching one of the included patterns, directories are still descended into either way.
+    pub fn add_include(&mut self, pattern: &str) -> crate::Result<&mut Self> {
+        self.include.push(parse_glob(pattern)?);
+        Ok(self)
+    }
+
+    /// Skips entries matching one of the excluded patterns, excluded directories are not descended into.
+    pub fn add_exclude(&mut self, pattern: &str) -> crate::Result<&mut Self> {
+        self.exclude.push(parse_glob(pattern)?);
+        Ok(self)
+    }
+
+    /// Descends into symbolic links to directories, each directory is visited at most once to prevent cycles.
+    pub fn set_follow_symlinks(&mut self, follow_symlinks: bool) -> &mut Self {
+        self.follow_symlinks = follow_symlinks;
+        self
+    }
+
+    /// Fetches the [`Metadata`] of every yielded entry, at the cost of an additional IPC call per entry.
+    pub fn set_metadata(&mut self, metadata: bool) -> &mut Self {
+        self.metadata = metadata;
+        self
+    }
+
+    fn matches(patterns: &[glob::Pattern], path: &Utf8Path) -> bool {
+        let options = glob::MatchOptions {
+            case_sensitive: true,
+            require_literal_separator: true,
+            require_literal_leading_dot: false,
+        };
+
+        patterns
+            .iter()
+            .any(|pattern| pattern.matches_with(path.as_str(), options))
+    }
+}
+
+fn parse_glob(pattern: &str) -> crate::Result<glob::Pattern> {
+    glob::Pattern::new(pattern)
+        .map_err(|err| Error::InvalidGlob(pattern.to_string(), err.msg.to_string()))
+}
+
+/// An entry yielded by [`walk`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct WalkEntry {
+    pub path: ScopedPath,
+    /// How far below the walked directory the entry is, starting at 1 for its direct children.
+    pub depth: usize,
+    pub is_dir: bool,
+    pub is_symlink: bool,
+    /// The metadata of the entry, only fetched if [`WalkOptions::set_metadata`] is enabled.
+    pub metadata: Option<Metadata>,
+}
+
+/// A non-recursive directory listing, older backends only report the path and name of an entry.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawDirEntry {
+    name: Option<String>,
+    path: Option<PathBuf>,
+    is_directory: Option<bool>,
+    #[serde(default)]
+    is_symlink: bool,
+}
+
+impl RawDirEntry {
+    fn name(&self) -> crate::Result<Option<&str>> {
+        if let Some(name) = &self.name {
+            return Ok(Some(name));
+        }
+
+        match self.path.as_deref().and_then(Path::file_name) {
+            Some(name) => name
+                .to_str()
+                .map(Some)
+                .ok_or_else(|| Error::Utf8(name.into())),
+            None => Ok(None),
+        }
+    }
+}
+
+struct Walker {
+    root: ScopedPath,
+    options: WalkOptions,
+    /// The directories currently being walked, the last one is the deepest.
+    stack: Vec<(ScopedPath, std::vec::IntoIter<RawDirEntry>)>,
+    /// The directory to read before continuing with the entries on the stack.
+    pending: Option<ScopedPath>,
+    /// The `(dev, ino)` pairs of the directories reached through symbolic links.
+    visited: HashSet<(u64, u64)>,
+}
+
+impl Walker {
+    async fn read_dir(path: &ScopedPath) -> crate::Result<Vec<RawDirEntry>> {
+        let raw = inner::readDir(
+            path.rel.as_str(),
+            serde_wasm_bindgen::to_value(&FsDirOptions {
+                dir: Some(path.base),
+                recursive: Some(false),
+            })?,
+        )
+        .await?;
+
+        Ok(serde_wasm_bindgen::from_value(raw)?)
+    }
+
+    async fn next(&mut self) -> Option<crate::Result<WalkEntry>> {
+        loop {
+            if let Some(dir) = self.pending.take() {
+                match Self::read_dir(&dir).await {
+                    Ok(entries) => self.stack.push((dir, entries.into_iter())),
+                    Err(err) => return Some(Err(err)),
+                }
+            }
+
+            let depth = self.stack.len();
+            let (parent, entries) = self.stack.last_mut()?;
+            let Some(raw) = entries.next() else {
+                self.stack.pop();
+                continue;
+            };
+
+            let path = match raw.name().and_then(|name| match name {
+                Some(name) => parent.join(name).map(Some),
+                None => Ok(None),
+            }) {
+                Ok(Some(path)) => path,
+                Ok(None) => continue,
+                Err(err) => return Some(Err(err)),
+            };
+
+            let relative = path
+                .rel
+                .strip_prefix(&self.root.rel)
+                .unwrap_or(&path.rel)
+                .to_owned();
+            if WalkOptions::matches(&self.options.exclude, &relative) {
+                continue;
+            }
+
+            let follow = raw.is_symlink && self.options.follow_symlinks;
+            let metadata = if self.options.metadata || follow || raw.is_directory.is_none() {
+                match metadata(&path).await {
+                    Ok(metadata) => Some(metadata),
+                    Err(err) => return Some(Err(err)),
+                }
+            } else {
+                None
+            };
+
+            let is_dir = match (&metadata, raw.is_directory) {
+                (Some(metadata), _) if follow => metadata.is_dir,
+                (_, Some(is_directory)) => is_directory,
+                (Some(metadata), None) => metadata.is_dir,
+                (None, None) => false,
+            };
+
+            // `Option::is_none_or` would raise the minimum supported Rust version to 1.82
+            #[allow(clippy::unnecessary_map_or)]
+            let below_max_depth = self.options.max_depth.map_or(true, |max| depth < max);
+            let descend =
+                is_dir && below_max_depth && (!raw.is_symlink || self.first_visit(&metadata));
+            if descend {
+                self.pending = Some(path.clone());
+            }
+
+            if self.options.include.is_empty()
+                || WalkOptions::matches(&self.options.include, &relative)
+            {
+                return Some(Ok(WalkEntry {
+                    path,
+                    depth,
+                    is_dir,
+                    is_symlink: raw.is_symlink,
+                    metadata: metadata.filter(|_| self.options.metadata),
+                }));
+            }
+        }
+    }
+
+    /// Checks that a directory reached through a symbolic link wasn't walked before.
+    fn first_visit(&mut self, metadata: &Option<Metadata>) -> bool {
+        if !self.options.follow_symlinks {
+            return false;
+        }
+
+        match metadata
+            .as_ref()
+            .and_then(|metadata| metadata.dev.zip(metadata.ino))
+        {
+            Some(id) => self.visited.insert(id),
+            // without an inode there's no way to detect cycles
+            None => false,
+        }
+    }
+}
+
+/// Walks a directory recursively, yielding its entries as they are read.
+///
+/// Unlike [`read_dir_all`] every directory is read with a separate IPC call once the walk reaches it,
+/// so the first entries are available right away and excluded directories are never read.
+/// Entries are yielded depth-first, a directory is yielded before its contents.
+///
+/// Errors reading a subdirectory or fetching metadata are yielded and the walk continues with the next entry.
+/// Nothing is read until the stream is polled.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use futures::StreamExt;
+/// use tauri_sys::fs::{self, BaseDirectory, ScopedPath, WalkOptions};
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut options = WalkOptions::new();
+/// options.add_include("**/*.jpg")?.set_metadata(true);
+///
+/// let mut entries = fs::walk(&ScopedPath::new(BaseDirectory::Picture, "")?, options);
+///
+/// while let Some(entry) = entries.next().await {
+///     let entry = entry?;
+///     let size = entry.metadata.map(|metadata| metadata.size).unwrap_or_default();
+///     log::debug!("{} has {} bytes", entry.path.rel(), size);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn walk(
+    path: &ScopedPath,
+    options: WalkOptions,
+) -> impl Stream<Item = crate::Result<WalkEntry>> {
+    let walker = Walker {
+        root: path.clone(),
+        options,
+        stack: Vec::new(),
+        pending: Some(path.clone()),
+        visited: HashSet::new(),
+    };
+
+    stream::unfold(walker, |mut walker| async move {
+        let entry = walker.next().await?;
+        Some((entry, walker))
+    })
+}
+
+/// Options for [`copy_dir`] and [`move_dir`].
+///
+/// By default the directories are merged, failing with an error when a file already exists in the destination.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct CopyOptions {
+    /// Replaces files that already exist in the destination.
+    pub overwrite: bool,
+    /// Leaves files that already exist in the destination untouched, takes precedence over `overwrite`.
+    pub skip_existing: bool,
+}
+
+/// The progress of [`copy_dir`] and [`move_dir`], yielded after every file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopyProgress {
+    /// The number of files copied or skipped so far.
+    pub files_done: u64,
+    /// The number of bytes copied so far, not counting skipped files.
+    pub bytes_done: u64,
+    /// The source path of the file that was just copied or skipped.
+    pub path: ScopedPath,
+    /// Whether the file was skipped because it already existed in the destination.
+    pub skipped: bool,
+}
+
+struct Transfer {
+    entries: stream::LocalBoxStream<'static, crate::Result<WalkEntry>>,
+    source: ScopedPath,
+    destination: ScopedPath,
+    options: CopyOptions,
+    /// Whether to move the files instead of copying them.
+    remove_source: bool,
+    started: bool,
+    files_done: u64,
+    files_skipped: u64,
+    bytes_done: u64,
+    /// The source directories in the order they were walked, only tracked when moving.
+    dirs: Vec<ScopedPath>,
+}
+
+impl Transfer {
+    fn new(
+        source: &ScopedPath,
+        destination: &ScopedPath,
+        options: CopyOptions,
+        remove_source: bool,
+    ) -> crate::Result<Self> {
+        source.shared_base(destination)?;
+
+        if destination.rel.starts_with(&source.rel) {
+            return Err(Error::DestinationInsideSource(
+                source.rel.clone().into(),
+                destination.rel.clone().into(),
+            ));
+        }
+
+        let mut walk_options = WalkOptions::new();
+        walk_options.set_metadata(true);
+
+        Ok(Self {
+            entries: walk(source, walk_options).boxed_local(),
+            source: source.clone(),
+            destination: destination.clone(),
+            options,
+            remove_source,
+            started: false,
+            files_done: 0,
+            files_skipped: 0,
+            bytes_done: 0,
+            dirs: Vec::new(),
+        })
+    }
+
+    async fn next(&mut self) -> crate::Result<Option<CopyProgress>> {
+        if !self.started {
+            self.started = true;
+            create_dir_all(&self.destination).await?;
+        }
+
+        while let Some(entry) = self.entries.next().await {
+            let entry = entry?;
+            let relative = entry
+                .path
+                .rel
+                .strip_prefix(&self.source.rel)
+                .unwrap_or(&entry.path.rel);
+            let target = self.destination.join(relative)?;
+
+            if entry.is_dir {
+                if !exists(&target).await? {
+                    create_dir(&target).await?;
+                }
+                if self.remove_source {
+                    self.dirs.push(entry.path);
+                }
+                continue;
+            }
+
+            let skipped = match exists(&target).await? {
+                true if self.options.skip_existing => true,
+                true if !self.options.overwrite => {
+                    return Err(Error::AlreadyExists(target.rel.into()))
+                }
+                _ => false,
+            };
+
+            if skipped {
+                self.files_skipped += 1;
+            } else {
+                if self.remove_source {
+                    rename_file(&entry.path, &target).await?;
+                } else {
+                    copy_file(&entry.path, &target).await?;
+                }
+                self.bytes_done += entry.metadata.map(|metadata| metadata.size).unwrap_or(0);
+            }
+            self.files_done += 1;
+
+            return Ok(Some(CopyProgress {
+                files_done: self.files_done,
+                bytes_done: self.bytes_done,
+                path: entry.path,
+                skipped,
+            }));
+        }
+
+        if self.remove_source {
+            // directories are walked before their contents, so removing them in reverse removes the deepest first
+            let dirs = std::mem::take(&mut self.dirs);
+            for dir in dirs.iter().rev().chain([&self.source]) {
+                if let Err(err) = remove_dir(dir).await {
+                    // directories holding skipped files are expected to remain
+                    if self.files_skipped == 0 {
+                        return Err(err);
+                    }
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn into_stream(self) -> impl Stream<Item = crate::Result<CopyProgress>> {
+        stream::try_unfold(self, |mut transfer| async move {
+            let progress = transfer.next().await?;
+            Ok(progress.map(|progress| (progress, transfer)))
+        })
+    }
+}
+
+/// Copies a directory recursively, merging it into the destination if that already exists.
+///
+/// Nothing is copied until the returned stream is polled, which yields the progress after every file.
+/// Dropping the stream cancels the copy once the file currently being copied is done,
+/// leaving the files copied so far in the destination.
+/// The stream ends after the first error.
+///
+/// Both paths must share a base directory and the destination must not be inside of the source.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use futures::StreamExt;
+/// use tauri_sys::fs::{self, BaseDirectory, CopyOptions, ScopedPath};
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut progress = fs::copy_dir(
+///     &ScopedPath::new(BaseDirectory::Document, "project")?,
+///     &ScopedPath::new(BaseDirectory::Document, "project backup")?,
+///     CopyOptions {
+///         overwrite: true,
+///         ..Default::default()
+///     },
+/// )?;
+///
+/// while let Some(progress) = progress.next().await {
+///     let progress = progress?;
+///     log::debug!("Copied {} files, {} bytes", progress.files_done, progress.bytes_done);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn copy_dir(
+    source: &ScopedPath,
+    destination: &ScopedPath,
+    options: CopyOptions,
+) -> crate::Result<impl Stream<Item = crate::Result<CopyProgress>>> {
+    Ok(Transfer::new(source, destination, options, false)?.into_stream())
+}
+
+/// Moves a directory recursively, merging it into the destination if that already exists.
+///
+/// Files are renamed one at a time and the emptied source directories are removed once every file was moved,
+/// skipped files are left in the source directory.
+/// Like [`copy_dir`] the returned stream yields the progress after every file and dropping it cancels the move.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use futures::TryStreamExt;
+/// use tauri_sys::fs::{self, BaseDirectory, CopyOptions, ScopedPath};
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let progress = fs::move_dir(
+///     &ScopedPath::new(BaseDirectory::Download, "photos")?,
+///     &ScopedPath::new(BaseDirectory::Download, "archive/photos")?,
+///     CopyOptions::default(),
+/// )?;
+///
+/// let last = progress.try_fold(None, |_, progress| async { Ok(Some(progress)) }).await?;
+/// log::debug!("Moved {} files", last.map(|progress| progress.files_done).unwrap_or(0));
+/// # Ok(())
+/// # }
+/// ```
+pub fn move_dir(
+    source: &ScopedPath,
+    destination: &ScopedPath,
+    options: CopyOptions,
+) -> crate::Result<impl Stream<Item = crate::Result<CopyProgress>>> {
+    Ok(Transfer::new(source, destination, options, true)?.into_stream())
+}
+
+/// A name under [`BaseDirectory::Temp`] that is unlikely to exist yet.
+fn temp_name(prefix: &str) -> crate::Result<ScopedPath> {
+    let random = || (js_sys::Math::random() * u32::MAX as f64) as u32;
+
+    ScopedPath::new(
+        BaseDirectory::Temp,
+        format!("{}{:08x}{:08x}", prefix, random(), random()),
+    )
+}
+
+/// Copies a file, streaming its contents through the frontend if the paths don't share a base directory.
+async fn copy_across(source: &ScopedPath, destination: &ScopedPath) -> crate::Result<()> {
+    if source.base == destination.base {
+        return copy_file(source, destination).await;
+    }
+
+    let mut chunks = Box::pin(read_stream(source, ReadStreamOptions::default()).await?);
+    let writer = FileWriter::create(destination).await?;
+    while let Some(chunk) = chunks.next().await {
+        writer.write(&chunk?).await?;
+    }
+
+    // the source is closed by the end of the stream, both have to be closed before the source can be removed
+    writer.close().await
+}
+
+/// A uniquely named directory under [`BaseDirectory::Temp`], removed with all of its contents when dropped.
+///
+/// Removing the directory when dropped happens in the background and failures are only logged,
+/// use [`TempDir::close`] to handle them.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tauri_sys::fs::{self, BaseDirectory, ScopedPath, TempDir};
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let build = TempDir::new("build-").await?;
+/// fs::write_text_file(&build.path().join("index.html")?, "<html></html>").await?;
+///
+/// build
+///     .persist(&ScopedPath::new(BaseDirectory::AppData, "site")?)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct TempDir {
+    path: ScopedPath,
+    removed: bool,
+}
+
+impl TempDir {
+    /// Creates a directory named after the prefix followed by random characters.
+    pub async fn new(prefix: &str) -> crate::Result<Self> {
+        let path = temp_name(prefix)?;
+        create_dir(&path).await?;
+
+        Ok(Self {
+            path,
+            removed: false,
+        })
+    }
+
+    pub fn path(&self) -> &ScopedPath {
+        &self.path
+    }
+
+    /// Moves the directory to its final location instead of removing it.
+    ///
+    /// Within [`BaseDirectory::Temp`] the directory is renamed,
+    /// otherwise its contents are copied to the destination before the directory is removed.
+    /// If persisting fails the directory is removed as if it was dropped.
+    pub async fn persist(mut self, destination: &ScopedPath) -> crate::Result<()> {
+        if self.path.base == destination.base {
+            rename_file(&self.path, destination).await?;
+            self.removed = true;
+            return Ok(());
+        }
+
+        create_dir_all(destination).await?;
+
+        let mut entries = Box::pin(walk(&self.path, WalkOptions::new()));
+        while let Some(entry) = entries.next().await {
+            let entry = entry?;
+            let relative = entry
+                .path
+                .rel
+                .strip_prefix(&self.path.rel)
+                .unwrap_or(&entry.path.rel);
+            let target = destination.join(relative)?;
+
+            if entry.is_dir {
+                create_dir_all(&target).await?;
+            } else {
+                copy_across(&entry.path, &target).await?;
+            }
+        }
+
+        self.close().await
+    }
+
+    /// Removes the directory and all of its contents, returning any error instead of logging it.
+    pub async fn close(mut self) -> crate::Result<()> {
+        self.removed = true;
+        remove_dir_all(&self.path).await
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        if self.removed {
+            return;
+        }
+
+        let path = self.path.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            if let Err(e) = remove_dir_all(&path).await {
+                log::error!("Failed to remove temporary directory: {}", e);
+            }
+        });
+    }
+}
+
+/// A uniquely named, initially empty file under [`BaseDirectory::Temp`], removed when dropped.
+///
+/// Removing the file when dropped happens in the background and failures are only logged,
+/// use [`NamedTempFile::close`] to handle them.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tauri_sys::fs::{self, BaseDirectory, NamedTempFile, OpenOptions, ScopedPath};
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let download = NamedTempFile::new("download-").await?;
+/// OpenOptions::new()
+///     .set_append(true)
+///     .write(download.path(), b"first chunk")
+///     .await?;
+///
+/// download
+///     .persist(&ScopedPath::new(BaseDirectory::Download, "video.mp4")?)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct NamedTempFile {
+    path: ScopedPath,
+    removed: bool,
+}
+
+impl NamedTempFile {
+    /// Creates a file named after the prefix followed by random characters.
+    pub async fn new(prefix: &str) -> crate::Result<Self> {
+        let path = temp_name(prefix)?;
+        OpenOptions::new()
+            .set_create_new(true)
+            .write(&path, &[])
+            .await?;
+
+        Ok(Self {
+            path,
+            removed: false,
+        })
+    }
+
+    pub fn path(&self) -> &ScopedPath {
+        &self.path
+    }
+
+    /// Moves the file to its final location instead of removing it, replacing any existing file.
+    ///
+    /// Within [`BaseDirectory::Temp`] the file is renamed,
+    /// otherwise it is copied to the destination before it is removed.
+    /// If persisting fails the file is removed as if it was dropped.
+    pub async fn persist(mut self, destination: &ScopedPath) -> crate::Result<()> {
+        if self.path.base == destination.base {
+            rename_file(&self.path, destination).await?;
+            self.removed = true;
+            return Ok(());
+        }
+
+        copy_across(&self.path, destination).await?;
+        self.close().await
+    }
+
+    /// Removes the file, returning any error instead of logging it.
+    pub async fn close(mut self) -> crate::Result<()> {
+        self.removed = true;
+        remove_file(&self.path).await
+    }
+}
+
+impl Drop for NamedTempFile {
+    fn drop(&mut self) {
+        if self.removed {
+            return;
+        }
+
+        let path = self.path.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            if let Err(e) = remove_file(&path).await {
+                log::error!("Failed to remove temporary file: {}", e);
+            }
+        });
+    }
 }
 
 mod inner {
-    use super::ArrayBuffer;
-    use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
+    use super::{ArrayBuffer, Uint8Array};
+    use wasm_bindgen::{
+        prelude::{wasm_bindgen, Closure},
+        JsValue,
+    };
 
     #[wasm_bindgen(module = "/src/fs.js")]
     extern "C" {
@@ -565,6 +2037,22 @@ mod inner {
             options: JsValue,
         ) -> Result<(), JsValue>;
         #[wasm_bindgen(catch)]
-        pub async fn metadata(path: &str) -> Result<JsValue, JsValue>;
+        pub async fn metadata(path: &str, options: JsValue) -> Result<JsValue, JsValue>;
+        #[wasm_bindgen(catch)]
+        pub async fn open(path: &str, options: JsValue) -> Result<JsValue, JsValue>;
+        #[wasm_bindgen(catch)]
+        pub async fn read(rid: u32, len: u32) -> Result<JsValue, JsValue>;
+        #[wasm_bindgen(catch)]
+        pub async fn write(rid: u32, data: Uint8Array) -> Result<JsValue, JsValue>;
+        #[wasm_bindgen(catch)]
+        pub async fn seek(rid: u32, offset: f64, whence: u32) -> Result<JsValue, JsValue>;
+        #[wasm_bindgen(catch)]
+        pub async fn close(rid: u32) -> Result<(), JsValue>;
+        #[wasm_bindgen(catch)]
+        pub async fn watch(
+            paths: JsValue,
+            options: JsValue,
+            handler: &Closure<dyn FnMut(JsValue)>,
+        ) -> Result<JsValue, JsValue>;
     }
 }
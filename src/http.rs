@@ -0,0 +1,396 @@
+//! Access the HTTP client written in Rust.
+//!
+//! The APIs must be added to tauri.allowlist.http in tauri.conf.json:
+//! ```json
+//! {
+//!     "tauri": {
+//!         "allowlist": {
+//!             "http": {
+//!                 "all": true, // enable all http APIs
+//!                 "request": true // enable HTTP request API
+//!             }
+//!         }
+//!     }
+//! }
+//! ```
+//! It is recommended to allowlist only the APIs you use for optimal bundle size and security.
+//!
+//! # Security
+//!
+//! This API has a scope configuration that forces you to restrict the URLs and paths that can be accessed using glob patterns.
+//!
+//! For instance, this scope configuration only allows making HTTP requests to the GitHub API for the `tauri-apps` organization:
+//! ```json
+//! {
+//!     "tauri": {
+//!         "allowlist": {
+//!             "http": {
+//!                 "scope": ["https://api.github.com/repos/tauri-apps/*"]
+//!             }
+//!         }
+//!     }
+//! }
+//! ```
+//! Trying to execute any API with a URL not configured on the scope results in a promise rejection due to denied access.
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_repr::Serialize_repr;
+use std::{collections::HashMap, path::PathBuf, time::Duration};
+use url::Url;
+use wasm_bindgen::JsCast;
+
+/// The HTTP request method.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Method {
+    #[default]
+    Get,
+    Post,
+    Put,
+    Delete,
+    Patch,
+    Head,
+    Options,
+    Connect,
+    Trace,
+}
+
+/// The type the response body is read as by the backend.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize_repr)]
+#[repr(u16)]
+pub enum ResponseType {
+    #[default]
+    Json = 1,
+    Text = 2,
+    Binary = 3,
+}
+
+/// The file contents of a [`FormPart::File`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum FilePart {
+    /// A path to a file on disk that will be read by the backend.
+    Path(PathBuf),
+    /// The raw file contents.
+    Bytes(Vec<u8>),
+}
+
+/// A single field of a multipart form body.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum FormPart {
+    Text(String),
+    Bytes(Vec<u8>),
+    #[serde(rename_all = "camelCase")]
+    File {
+        file: FilePart,
+        mime: Option<String>,
+        file_name: Option<String>,
+    },
+}
+
+/// The body of a request.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", content = "payload")]
+pub enum Body {
+    /// A JSON body, sent with the `application/json` content type.
+    Json(serde_json::Value),
+    /// A plain text body.
+    Text(String),
+    /// A raw binary body.
+    Bytes(Vec<u8>),
+    /// A `multipart/form-data` body.
+    Form(HashMap<String, FormPart>),
+}
+
+impl Body {
+    /// Creates a JSON body from any serializable value.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tauri_sys::http::Body;
+    /// use serde::Serialize;
+    ///
+    /// #[derive(Serialize)]
+    /// struct Login<'a> {
+    ///     user: &'a str,
+    /// }
+    ///
+    /// let body = Body::json(&Login { user: "tauri" })?;
+    /// ```
+    pub fn json<T: Serialize>(value: &T) -> crate::Result<Self> {
+        serde_json::to_value(value)
+            .map(Self::Json)
+            .map_err(|e| crate::Error::Serde(e.to_string()))
+    }
+}
+
+/// Response object.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Response<T> {
+    /// The request URL.
+    pub url: Url,
+    /// The response status code.
+    pub status: u16,
+    /// A boolean indicating whether the response was successful (status in the range 200–299) or not.
+    pub ok: bool,
+    /// The response headers.
+    pub headers: HashMap<String, String>,
+    /// The response raw headers.
+    pub raw_headers: HashMap<String, Vec<String>>,
+    /// The response data.
+    pub data: T,
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ClientOptions {
+    max_redirections: Option<u32>,
+    connect_timeout: Option<Duration>,
+}
+
+/// A builder for [`Client`]s.
+#[derive(Debug, Default, Clone)]
+pub struct ClientBuilder {
+    inner: ClientOptions,
+}
+
+impl ClientBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Defines the maximum number of redirects the client should follow.
+    ///
+    /// If set to 0, no redirects will be followed.
+    pub fn set_max_redirections(&mut self, max_redirections: u32) -> &mut Self {
+        self.inner.max_redirections = Some(max_redirections);
+        self
+    }
+
+    /// The timeout for establishing a connection.
+    pub fn set_connect_timeout(&mut self, connect_timeout: Duration) -> &mut Self {
+        self.inner.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    /// Creates a new HTTP client on the backend.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tauri_sys::http::ClientBuilder;
+    ///
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ClientBuilder::new().set_max_redirections(3).build().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn build(&self) -> crate::Result<Client> {
+        let raw = inner::getClient(to_value(&self.inner)?).await?;
+
+        Ok(Client(raw.unchecked_into()))
+    }
+}
+
+/// A HTTP client that lives on the backend.
+///
+/// The backend client is automatically dropped when this handle goes out of scope.
+#[derive(Debug)]
+pub struct Client(inner::Client);
+
+impl Client {
+    /// Creates a new HTTP client with the default options.
+    ///
+    /// See [`ClientBuilder`] for configuring the client.
+    pub async fn new() -> crate::Result<Self> {
+        ClientBuilder::new().build().await
+    }
+
+    /// Creates a request builder for the given method and URL.
+    pub fn request<'a>(&'a self, method: Method, url: &'a str) -> RequestBuilder<'a> {
+        RequestBuilder {
+            client: self,
+            inner: RequestOptions {
+                method,
+                url,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Creates a `GET` request builder.
+    pub fn get<'a>(&'a self, url: &'a str) -> RequestBuilder<'a> {
+        self.request(Method::Get, url)
+    }
+
+    /// Creates a `POST` request builder.
+    pub fn post<'a>(&'a self, url: &'a str) -> RequestBuilder<'a> {
+        self.request(Method::Post, url)
+    }
+
+    /// Creates a `PUT` request builder.
+    pub fn put<'a>(&'a self, url: &'a str) -> RequestBuilder<'a> {
+        self.request(Method::Put, url)
+    }
+
+    /// Creates a `PATCH` request builder.
+    pub fn patch<'a>(&'a self, url: &'a str) -> RequestBuilder<'a> {
+        self.request(Method::Patch, url)
+    }
+
+    /// Creates a `DELETE` request builder.
+    pub fn delete<'a>(&'a self, url: &'a str) -> RequestBuilder<'a> {
+        self.request(Method::Delete, url)
+    }
+}
+
+impl Drop for Client {
+    fn drop(&mut self) {
+        log::debug!("Dropping HTTP client");
+        let client = self.0.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            if let Err(e) = client.drop().await {
+                log::error!("Failed to drop HTTP client: {:?}", e);
+            }
+        });
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RequestOptions<'a> {
+    method: Method,
+    url: &'a str,
+    headers: HashMap<String, String>,
+    query: HashMap<String, String>,
+    body: Option<Body>,
+    timeout: Option<Duration>,
+    response_type: ResponseType,
+}
+
+/// A builder for HTTP requests, created through the methods on [`Client`].
+#[derive(Debug, Clone)]
+pub struct RequestBuilder<'a> {
+    client: &'a Client,
+    inner: RequestOptions<'a>,
+}
+
+impl<'a> RequestBuilder<'a> {
+    /// Adds a request header.
+    pub fn add_header(&mut self, key: impl Into<String>, value: impl Into<String>) -> &mut Self {
+        self.inner.headers.insert(key.into(), value.into());
+        self
+    }
+
+    /// Adds many request headers.
+    pub fn add_headers<K, V>(&mut self, headers: impl IntoIterator<Item = (K, V)>) -> &mut Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        for (key, value) in headers.into_iter() {
+            self.add_header(key, value);
+        }
+        self
+    }
+
+    /// Adds a query parameter to the URL.
+    pub fn add_query(&mut self, key: impl Into<String>, value: impl Into<String>) -> &mut Self {
+        self.inner.query.insert(key.into(), value.into());
+        self
+    }
+
+    /// Sets the request body.
+    pub fn set_body(&mut self, body: Body) -> &mut Self {
+        self.inner.body = Some(body);
+        self
+    }
+
+    /// Sets the timeout for the whole request.
+    pub fn set_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.inner.timeout = Some(timeout);
+        self
+    }
+
+    /// Sends the request and deserializes the JSON response body into `T`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tauri_sys::http::Client;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Repo {
+    ///     stargazers_count: u32,
+    /// }
+    ///
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new().await?;
+    ///
+    /// let res = client
+    ///     .get("https://api.github.com/repos/tauri-apps/tauri")
+    ///     .add_header("User-Agent", "tauri-sys")
+    ///     .send::<Repo>()
+    ///     .await?;
+    ///
+    /// println!("{} stars", res.data.stargazers_count);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn send<T: DeserializeOwned>(&self) -> crate::Result<Response<T>> {
+        self.send_as(ResponseType::Json).await
+    }
+
+    /// Sends the request and returns the response body as text.
+    pub async fn text(&self) -> crate::Result<Response<String>> {
+        self.send_as(ResponseType::Text).await
+    }
+
+    /// Sends the request and returns the raw response body.
+    pub async fn bytes(&self) -> crate::Result<Response<Vec<u8>>> {
+        self.send_as(ResponseType::Binary).await
+    }
+
+    async fn send_as<T: DeserializeOwned>(
+        &self,
+        response_type: ResponseType,
+    ) -> crate::Result<Response<T>> {
+        let options = RequestOptions {
+            response_type,
+            ..self.inner.clone()
+        };
+
+        let raw = self.client.0.request(to_value(&options)?).await?;
+
+        Ok(serde_wasm_bindgen::from_value(raw)?)
+    }
+}
+
+/// The backend expects plain objects for maps and `null` for missing values.
+fn to_value<T: Serialize>(value: &T) -> crate::Result<wasm_bindgen::JsValue> {
+    Ok(value.serialize(&serde_wasm_bindgen::Serializer::json_compatible())?)
+}
+
+mod inner {
+    use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
+
+    #[wasm_bindgen(module = "/src/http.js")]
+    extern "C" {
+        #[derive(Debug, Clone)]
+        pub type Client;
+        #[wasm_bindgen(method, catch)]
+        pub async fn drop(this: &Client) -> Result<JsValue, JsValue>;
+        #[wasm_bindgen(method, catch)]
+        pub async fn request(this: &Client, options: JsValue) -> Result<JsValue, JsValue>;
+    }
+
+    #[wasm_bindgen(module = "/src/http.js")]
+    extern "C" {
+        #[wasm_bindgen(catch)]
+        pub async fn getClient(options: JsValue) -> Result<JsValue, JsValue>;
+    }
+}
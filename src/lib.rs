@@ -86,6 +86,10 @@
 //! This is rarely a concern in practice, but if you need to suspend processing of events for a long time,
 //! you should rather drop the entire stream and re-create it as needed later.
 //!
+//! For high-frequency events, [`event::listen_with`] creates a stream backed by a bounded buffer instead.
+//! Its [`event::Overflow`] policy decides whether the oldest or the newest events get dropped once the buffer is full,
+//! or whether the stream should coalesce to the latest value.
+//!
 //! ### Cancelling Streams
 //!
 //! One usecase of the `unlisten` function might intuitively not map well to streams: Cancellation.
@@ -120,6 +124,8 @@
 
 #[cfg(feature = "app")]
 pub mod app;
+#[cfg(feature = "cli")]
+pub mod cli;
 #[cfg(feature = "clipboard")]
 pub mod clipboard;
 #[cfg(feature = "dialog")]
@@ -131,6 +137,10 @@ pub mod event;
 pub mod fs;
 #[cfg(feature = "global_shortcut")]
 pub mod global_shortcut;
+#[cfg(feature = "http")]
+pub mod http;
+#[cfg(feature = "log")]
+pub mod log;
 #[cfg(feature = "mocks")]
 pub mod mocks;
 #[cfg(feature = "notification")]
@@ -143,6 +153,8 @@ pub mod path;
 pub mod positioner;
 #[cfg(feature = "process")]
 pub mod process;
+#[cfg(feature = "shell")]
+pub mod shell;
 #[cfg(feature = "store")]
 pub mod store;
 #[cfg(feature = "tauri")]
@@ -0,0 +1,523 @@
+//! Forward log records to the backend and mirror backend logs into the webview.
+//!
+//! The Log plugin must be installed and configured.
+//! ```rust
+//! tauri::Builder::default()
+//!   .plugin(tauri_plugin_log::Builder::default().build())
+//!   .run(tauri::generate_context!())
+//! ```
+//!
+//! When the app isn't running inside of Tauri, e.g. while developing the frontend in a regular browser,
+//! records are written to the browser console instead.
+//!
+//! With the `tracing` feature enabled, [`TauriLayer`] forwards the events and spans of the `tracing` crate the same way.
+use crate::event::{listen, EventOptions};
+use ::log::{LevelFilter, Metadata, Record, SetLoggerError};
+use futures::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Mutex, PoisonError,
+};
+
+/// The records waiting to be sent to the backend.
+static QUEUE: Mutex<Vec<LogArgs>> = Mutex::new(Vec::new());
+/// Whether a task is sending the queued records.
+static SENDING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Serialize_repr, Deserialize_repr, Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(u8)]
+enum Level {
+    Trace = 1,
+    Debug = 2,
+    Info = 3,
+    Warn = 4,
+    Error = 5,
+}
+
+impl From<::log::Level> for Level {
+    fn from(level: ::log::Level) -> Self {
+        match level {
+            ::log::Level::Error => Level::Error,
+            ::log::Level::Warn => Level::Warn,
+            ::log::Level::Info => Level::Info,
+            ::log::Level::Debug => Level::Debug,
+            ::log::Level::Trace => Level::Trace,
+        }
+    }
+}
+
+impl From<Level> for ::log::Level {
+    fn from(level: Level) -> Self {
+        match level {
+            Level::Error => ::log::Level::Error,
+            Level::Warn => ::log::Level::Warn,
+            Level::Info => ::log::Level::Info,
+            Level::Debug => ::log::Level::Debug,
+            Level::Trace => ::log::Level::Trace,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct LogArgs {
+    level: Level,
+    message: String,
+    location: String,
+    file: Option<String>,
+    line: Option<u32>,
+}
+
+/// Configures and installs the logger forwarding records to the backend.
+///
+/// Records are queued and sent once the current task yields or the batch is full, so logging doesn't wait on the IPC.
+/// Every record is sent unchanged and in the order it was logged.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use log::LevelFilter;
+/// use tauri_sys::log::LoggerBuilder;
+///
+/// LoggerBuilder::new()
+///     .set_level(LevelFilter::Debug)
+///     .install()
+///     .expect("a logger was already installed");
+///
+/// log::info!("Hello from the frontend");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LoggerBuilder {
+    level: LevelFilter,
+    max_batch: usize,
+}
+
+impl Default for LoggerBuilder {
+    fn default() -> Self {
+        Self {
+            level: LevelFilter::Info,
+            max_batch: MAX_BATCH,
+        }
+    }
+}
+
+impl LoggerBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the most verbose level that is forwarded, defaults to [`LevelFilter::Info`].
+    pub fn set_level(&mut self, level: LevelFilter) -> &mut Self {
+        self.level = level;
+        self
+    }
+
+    /// Set the maximum number of records buffered before they are sent, values smaller than 1 are treated as 1.
+    ///
+    /// A value of 1 sends every record right away.
+    pub fn set_max_batch(&mut self, max_batch: usize) -> &mut Self {
+        self.max_batch = max_batch.max(1);
+        self
+    }
+
+    /// Installs the logger as the global logger of the [`log`](::log) crate.
+    ///
+    /// Fails if a global logger was installed before.
+    pub fn install(&self) -> Result<(), SetLoggerError> {
+        let logger = Box::leak(Box::new(TauriLogger {
+            level: self.level,
+            max_batch: self.max_batch,
+        }));
+
+        ::log::set_logger(logger)?;
+        ::log::set_max_level(self.level);
+
+        Ok(())
+    }
+}
+
+/// The default for [`LoggerBuilder::set_max_batch`].
+const MAX_BATCH: usize = 64;
+
+struct TauriLogger {
+    level: LevelFilter,
+    max_batch: usize,
+}
+
+impl ::log::Log for TauriLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let args = LogArgs {
+            level: record.level().into(),
+            message: format!("{}", record.args()),
+            location: record.target().to_string(),
+            file: record.file().map(ToString::to_string),
+            line: record.line(),
+        };
+
+        dispatch(args, self.max_batch);
+    }
+
+    fn flush(&self) {
+        flush_queue();
+    }
+}
+
+/// Queues a record to be sent to the backend, or writes it to the console when not running inside of Tauri.
+fn dispatch(args: LogArgs, max_batch: usize) {
+    if !is_tauri() {
+        write_console(args.level, &format!("[{}] {}", args.location, args.message));
+        return;
+    }
+
+    let mut queue = QUEUE.lock().unwrap_or_else(PoisonError::into_inner);
+    queue.push(args);
+
+    if queue.len() >= max_batch {
+        drop(queue);
+        flush_queue();
+    } else if queue.len() == 1 {
+        // send everything logged until the current task yields
+        wasm_bindgen_futures::spawn_local(async { flush_queue() });
+    }
+}
+
+/// Sends the queued records to the backend.
+fn flush_queue() {
+    if SENDING.swap(true, Ordering::AcqRel) {
+        // the running task picks up the records queued in the meantime
+        return;
+    }
+
+    // a single task sends the records one after another, so the backend receives them in the order they were logged
+    wasm_bindgen_futures::spawn_local(async {
+        loop {
+            let records =
+                std::mem::take(&mut *QUEUE.lock().unwrap_or_else(PoisonError::into_inner));
+            if records.is_empty() {
+                break;
+            }
+
+            for args in records {
+                if let Err(e) = crate::tauri::invoke::<_, ()>("plugin:log|log", &args).await {
+                    // logging the failure would end up in this logger again
+                    inner::error(&format!("Failed to forward log record: {}", e));
+                    write_console(args.level, &format!("[{}] {}", args.location, args.message));
+                }
+            }
+        }
+
+        SENDING.store(false, Ordering::Release);
+    });
+}
+
+fn is_tauri() -> bool {
+    js_sys::Reflect::get(&js_sys::global(), &"__TAURI_INTERNALS__".into())
+        .map(|internals| internals.is_object())
+        .unwrap_or(false)
+}
+
+fn write_console(level: Level, message: &str) {
+    match level {
+        Level::Trace => inner::trace(message),
+        Level::Debug => inner::debug(message),
+        Level::Info => inner::info(message),
+        Level::Warn => inner::warn(message),
+        Level::Error => inner::error(message),
+    }
+}
+
+/// Removes the ANSI escape sequences the backend uses to color its output.
+fn strip_ansi(message: &str) -> String {
+    let mut stripped = String::with_capacity(message.len());
+    let mut chars = message.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            if chars.next() == Some('[') {
+                for c in chars.by_ref() {
+                    if c.is_ascii_alphabetic() {
+                        break;
+                    }
+                }
+            }
+        } else {
+            stripped.push(c);
+        }
+    }
+
+    stripped
+}
+
+/// A record logged by the backend.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogEvent {
+    pub level: ::log::Level,
+    pub message: String,
+}
+
+#[derive(Deserialize)]
+struct RawLogEvent {
+    level: Level,
+    message: String,
+}
+
+/// Mirrors the records logged by the backend into the browser console.
+///
+/// Every record is written to the console as the returned stream yields it, so the stream needs to be driven for the records to show up.
+/// The returned Stream will automatically clean up it's underlying event listener when dropped, so no manual unlisten function needs to be called.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use futures::StreamExt;
+/// use tauri_sys::log::attach_console;
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let records = attach_console().await?;
+///
+/// wasm_bindgen_futures::spawn_local(records.for_each(|_| async {}));
+/// # Ok(())
+/// # }
+/// ```
+pub async fn attach_console() -> crate::Result<impl Stream<Item = LogEvent>> {
+    let events = listen::<RawLogEvent>("log://log", EventOptions::default()).await?;
+
+    Ok(events.map(|event| {
+        let message = strip_ansi(&event.payload.message);
+        write_console(event.payload.level, &message);
+
+        LogEvent {
+            level: event.payload.level.into(),
+            message,
+        }
+    }))
+}
+
+/// A [`Layer`](tracing_subscriber::Layer) forwarding `tracing` events and spans to the backend.
+///
+/// Events are forwarded like records of the [`log`](::log) crate, prefixed with the names of the spans they occurred in.
+/// Entering and exiting a span is forwarded as well, together with the fields and the id of the span,
+/// so the output of the frontend can be correlated with the output of the backend.
+/// Records are queued and sent the same way as by the logger installed by [`LoggerBuilder`].
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tauri_sys::log::TauriLayer;
+/// use tracing_subscriber::prelude::*;
+///
+/// tracing_subscriber::registry().with(TauriLayer::new()).init();
+///
+/// let span = tracing::info_span!("load", file = "settings.json");
+/// let _guard = span.enter();
+/// tracing::info!(entries = 3, "loaded settings");
+/// ```
+#[cfg(feature = "tracing")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TauriLayer {
+    max_batch: usize,
+}
+
+#[cfg(feature = "tracing")]
+impl Default for TauriLayer {
+    fn default() -> Self {
+        Self {
+            max_batch: MAX_BATCH,
+        }
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl TauriLayer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum number of records buffered before they are sent, values smaller than 1 are treated as 1.
+    pub fn set_max_batch(&mut self, max_batch: usize) -> &mut Self {
+        self.max_batch = max_batch.max(1);
+        self
+    }
+
+    fn forward(&self, metadata: &tracing::Metadata<'_>, message: String) {
+        let args = LogArgs {
+            level: (*metadata.level()).into(),
+            message,
+            location: metadata.target().to_string(),
+            file: metadata.file().map(ToString::to_string),
+            line: metadata.line(),
+        };
+
+        dispatch(args, self.max_batch);
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl From<tracing::Level> for Level {
+    fn from(level: tracing::Level) -> Self {
+        match level {
+            tracing::Level::ERROR => Level::Error,
+            tracing::Level::WARN => Level::Warn,
+            tracing::Level::INFO => Level::Info,
+            tracing::Level::DEBUG => Level::Debug,
+            tracing::Level::TRACE => Level::Trace,
+        }
+    }
+}
+
+/// The fields recorded for a span, stored in the span's extensions.
+#[cfg(feature = "tracing")]
+#[derive(Debug, Default)]
+struct SpanFields(String);
+
+/// Collects fields as `name=value` pairs, keeping the `message` field of events separate.
+#[cfg(feature = "tracing")]
+#[derive(Debug, Default)]
+struct FieldVisitor {
+    message: Option<String>,
+    fields: String,
+}
+
+#[cfg(feature = "tracing")]
+impl FieldVisitor {
+    fn push(&mut self, field: &tracing::field::Field, value: std::fmt::Arguments<'_>) {
+        if field.name() == "message" {
+            self.message = Some(value.to_string());
+            return;
+        }
+
+        if !self.fields.is_empty() {
+            self.fields.push(' ');
+        }
+        self.fields.push_str(&format!("{}={}", field.name(), value));
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl tracing::field::Visit for FieldVisitor {
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        self.push(field, format_args!("{}", value));
+    }
+
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        self.push(field, format_args!("{:?}", value));
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl<S> tracing_subscriber::Layer<S> for TauriLayer
+where
+    S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+{
+    fn on_new_span(
+        &self,
+        attrs: &tracing::span::Attributes<'_>,
+        id: &tracing::span::Id,
+        ctx: tracing_subscriber::layer::Context<'_, S>,
+    ) {
+        let mut visitor = FieldVisitor::default();
+        attrs.record(&mut visitor);
+
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(SpanFields(visitor.fields));
+        }
+    }
+
+    fn on_record(
+        &self,
+        id: &tracing::span::Id,
+        values: &tracing::span::Record<'_>,
+        ctx: tracing_subscriber::layer::Context<'_, S>,
+    ) {
+        let mut visitor = FieldVisitor::default();
+        values.record(&mut visitor);
+
+        if let Some(span) = ctx.span(id) {
+            let mut extensions = span.extensions_mut();
+            match extensions.get_mut::<SpanFields>() {
+                Some(SpanFields(fields)) if !fields.is_empty() => {
+                    fields.push(' ');
+                    fields.push_str(&visitor.fields);
+                }
+                Some(fields) => fields.0 = visitor.fields,
+                None => extensions.insert(SpanFields(visitor.fields)),
+            }
+        }
+    }
+
+    fn on_event(&self, event: &tracing::Event<'_>, ctx: tracing_subscriber::layer::Context<'_, S>) {
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+
+        let mut message = String::new();
+        if let Some(scope) = ctx.event_scope(event) {
+            for span in scope.from_root() {
+                message.push_str(span.name());
+                message.push(':');
+            }
+            if !message.is_empty() {
+                message.push(' ');
+            }
+        }
+        message.push_str(visitor.message.as_deref().unwrap_or_default());
+        if !visitor.fields.is_empty() {
+            if visitor.message.is_some() {
+                message.push(' ');
+            }
+            message.push_str(&visitor.fields);
+        }
+
+        self.forward(event.metadata(), message);
+    }
+
+    fn on_enter(&self, id: &tracing::span::Id, ctx: tracing_subscriber::layer::Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            let extensions = span.extensions();
+            let fields = extensions
+                .get::<SpanFields>()
+                .map(|fields| fields.0.as_str())
+                .unwrap_or_default();
+
+            self.forward(
+                span.metadata(),
+                format!("enter {}{{{}}} [{}]", span.name(), fields, id.into_u64()),
+            );
+        }
+    }
+
+    fn on_exit(&self, id: &tracing::span::Id, ctx: tracing_subscriber::layer::Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            self.forward(
+                span.metadata(),
+                format!("exit {} [{}]", span.name(), id.into_u64()),
+            );
+        }
+    }
+}
+
+mod inner {
+    use wasm_bindgen::prelude::wasm_bindgen;
+
+    #[wasm_bindgen]
+    extern "C" {
+        #[wasm_bindgen(js_namespace = console)]
+        pub fn trace(message: &str);
+        #[wasm_bindgen(js_namespace = console)]
+        pub fn debug(message: &str);
+        #[wasm_bindgen(js_namespace = console)]
+        pub fn info(message: &str);
+        #[wasm_bindgen(js_namespace = console)]
+        pub fn warn(message: &str);
+        #[wasm_bindgen(js_namespace = console)]
+        pub fn error(message: &str);
+    }
+}
@@ -1,4 +1,8 @@
 // tauri/tooling/api/src/mocks.ts
+function mockInternals() {
+  window.__TAURI_INTERNALS__ = window.__TAURI_INTERNALS__ ?? {};
+}
+
 function mockIPC(cb) {
   window.__TAURI_IPC__ = async ({
     cmd,
@@ -12,16 +16,60 @@ function mockIPC(cb) {
       window[`_${error}`](err);
     }
   };
+
+  mockInternals();
+
+  window.__TAURI_INTERNALS__.transformCallback = function transformCallback(
+    callback,
+    once = false
+  ) {
+    const identifier = window.crypto.getRandomValues(new Uint32Array(1))[0];
+    const prop = `_${identifier}`;
+
+    Object.defineProperty(window, prop, {
+      value: (result) => {
+        if (once) {
+          Reflect.deleteProperty(window, prop);
+        }
+
+        return callback && callback(result);
+      },
+      writable: false,
+      configurable: true,
+    });
+
+    return identifier;
+  };
+
+  window.__TAURI_INTERNALS__.invoke = async function (cmd, args) {
+    return cb(cmd, args ?? {});
+  };
 }
 function mockWindows(current, ...additionalWindows) {
+  const windows = [current, ...additionalWindows].map((label) => ({ label }));
+
   window.__TAURI_METADATA__ = {
-    __windows: [current, ...additionalWindows].map((label) => ({ label })),
+    __windows: windows,
     __currentWindow: { label: current }
   };
+
+  mockInternals();
+  window.__TAURI_INTERNALS__.windows = windows;
+  window.__TAURI_INTERNALS__.metadata = {
+    windows,
+    currentWindow: { label: current }
+  };
 }
 function clearMocks() {
   delete window.__TAURI_IPC__;
   delete window.__TAURI_METADATA__;
+
+  if (window.__TAURI_INTERNALS__) {
+    delete window.__TAURI_INTERNALS__.invoke;
+    delete window.__TAURI_INTERNALS__.transformCallback;
+    delete window.__TAURI_INTERNALS__.windows;
+    delete window.__TAURI_INTERNALS__.metadata;
+  }
 }
 export {
   clearMocks,
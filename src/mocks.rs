@@ -1,7 +1,19 @@
-//! 
+//! Mock the Tauri runtime, for testing frontends without a backend or when running in a browser.
 
-use js_sys::Array;
-use wasm_bindgen::{prelude::Closure, JsValue};
+use js_sys::{Array, Function, Object, Reflect};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+#[cfg(feature = "fs")]
+use crate::fs::BaseDirectory;
+#[cfg(feature = "window")]
+use crate::window::Theme;
+#[cfg(feature = "fs")]
+use std::{
+    collections::BTreeMap,
+    path::{Component, Path, PathBuf},
+};
+use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
 
 /// Mocks the current window label
 /// In non-tauri context it is required to call this function///before* using the `@tauri-apps/api/window` module.
@@ -45,9 +57,2019 @@ where
     closure.forget();
 }
 
+type Handler = Box<dyn FnMut(JsValue) -> Result<JsValue, JsValue>>;
+
+/// A call intercepted by a [`MockRouter`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockCall {
+    /// The name of the command, built-in module commands are named `Module.command`, e.g. `Fs.readTextFile`.
+    pub command: String,
+    /// The arguments the command was called with.
+    pub payload: JsValue,
+}
+
+/// The calls recorded by an installed [`MockRouter`].
+#[derive(Debug, Clone, Default)]
+pub struct MockCalls(Rc<RefCell<Vec<MockCall>>>);
+
+impl MockCalls {
+    /// Returns all calls in the order they were made.
+    pub fn all(&self) -> Vec<MockCall> {
+        self.0.borrow().clone()
+    }
+
+    /// Returns the calls made to the given command.
+    pub fn to(&self, command: &str) -> Vec<MockCall> {
+        self.0
+            .borrow()
+            .iter()
+            .filter(|call| call.command == command)
+            .cloned()
+            .collect()
+    }
+
+    /// Returns how many times the given command was called.
+    pub fn count(&self, command: &str) -> usize {
+        self.0
+            .borrow()
+            .iter()
+            .filter(|call| call.command == command)
+            .count()
+    }
+
+    /// Forgets all recorded calls.
+    pub fn clear(&self) {
+        self.0.borrow_mut().clear();
+    }
+}
+
+/// Routes intercepted IPC requests to typed handlers.
+///
+/// Handlers are registered per custom command, or per command of the built-in modules, such as `Fs.readTextFile` or `App.getAppVersion`.
+/// Every call is recorded, and calls to commands without a handler are rejected with an error naming the command.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use serde::Deserialize;
+/// use tauri_sys::mocks::MockRouter;
+///
+/// #[derive(Deserialize)]
+/// struct AddArgs {
+///     a: u32,
+///     b: u32,
+/// }
+///
+/// let calls = MockRouter::new()
+///     .add_command("add", |args: AddArgs| Ok::<_, String>(args.a + args.b))
+///     .add_module_command("App", "getAppVersion", |_: serde::de::IgnoredAny| {
+///         Ok::<_, String>("1.0.0")
+///     })
+///     .install();
+///
+/// // ... run the code under test
+///
+/// assert_eq!(calls.count("add"), 1);
+/// ```
+#[derive(Default)]
+pub struct MockRouter {
+    handlers: HashMap<String, Handler>,
+}
+
+impl MockRouter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a handler for a custom command.
+    ///
+    /// The arguments of the command are deserialized into `A`, the handler's result is serialized and returned to the caller.
+    /// An `Err` returned by the handler rejects the call with the serialized error.
+    pub fn add_command<A, R, E, F>(&mut self, command: &str, mut handler: F) -> &mut Self
+    where
+        A: DeserializeOwned,
+        R: Serialize,
+        E: Serialize,
+        F: FnMut(A) -> Result<R, E> + 'static,
+    {
+        let command_name = command.to_string();
+
+        self.add_raw_command(command, move |payload| {
+            let args = serde_wasm_bindgen::from_value(payload).map_err(|e| {
+                JsValue::from_str(&format!(
+                    "invalid arguments for mocked command `{}`: {}",
+                    command_name, e
+                ))
+            })?;
+
+            match handler(args) {
+                Ok(value) => to_value(&value),
+                Err(err) => Err(to_value(&err)?),
+            }
+        })
+    }
+
+    /// Registers a handler for a command of a built-in module, e.g. `add_module_command("Fs", "readTextFile", ...)`.
+    ///
+    /// The message sent by the module is deserialized into `A`.
+    pub fn add_module_command<A, R, E, F>(
+        &mut self,
+        module: &str,
+        command: &str,
+        handler: F,
+    ) -> &mut Self
+    where
+        A: DeserializeOwned,
+        R: Serialize,
+        E: Serialize,
+        F: FnMut(A) -> Result<R, E> + 'static,
+    {
+        self.add_command(&format!("{}.{}", module, command), handler)
+    }
+
+    /// Registers an untyped handler receiving the raw arguments of the command.
+    ///
+    /// Built-in module commands are registered as `Module.command`.
+    pub fn add_raw_command<F>(&mut self, command: &str, handler: F) -> &mut Self
+    where
+        F: FnMut(JsValue) -> Result<JsValue, JsValue> + 'static,
+    {
+        self.handlers.insert(command.to_string(), Box::new(handler));
+        self
+    }
+
+    /// Intercepts all IPC requests with the registered handlers, returning the record of calls.
+    ///
+    /// This replaces any handler installed before through [`mock_ipc`] or another router.
+    pub fn install(&mut self) -> MockCalls {
+        let mut handlers = std::mem::take(&mut self.handlers);
+        let calls = MockCalls::default();
+        let recorded = calls.clone();
+
+        mock_ipc(move |cmd: String, payload: JsValue| {
+            let (command, payload) = route(cmd, payload);
+
+            recorded.0.borrow_mut().push(MockCall {
+                command: command.clone(),
+                payload: payload.clone(),
+            });
+
+            match handlers.get_mut(&command) {
+                Some(handler) => handler(payload),
+                None => Err(JsValue::from_str(&format!(
+                    "no mock registered for command `{}`",
+                    command
+                ))),
+            }
+        });
+
+        calls
+    }
+}
+
+/// An event emitted by the frontend and captured by [`MockEvents`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockEvent {
+    /// The name of the event.
+    pub event: String,
+    /// The payload of the event.
+    pub payload: JsValue,
+    /// The label of the window the event was targeted at, `None` if it was sent to all windows.
+    pub window_label: Option<String>,
+}
+
+#[derive(Debug)]
+struct MockListener {
+    id: u32,
+    event: String,
+    window_label: Option<String>,
+    handler: u32,
+}
+
+#[derive(Debug, Default)]
+struct EventHub {
+    next_id: u32,
+    listeners: Vec<MockListener>,
+    emitted: Vec<MockEvent>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UnlistenArgs {
+    event_id: u32,
+}
+
+thread_local! {
+    static CURRENT_EVENTS: RefCell<Option<MockEvents>> = const { RefCell::new(None) };
+}
+
+/// An in-memory event bus answering the event commands, so event streams can be tested without a Tauri runtime.
+///
+/// Events emitted by the frontend are captured and delivered to the frontend's own listeners,
+/// events from the backend are simulated through [`mock_emit`] and [`mock_emit_to`].
+/// Events targeted at a window are only delivered to listeners of that window and to listeners without a target.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use futures::StreamExt;
+/// use tauri_sys::{event::{listen, EventOptions}, mocks::{mock_emit, MockEvents, MockRouter}};
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut router = MockRouter::new();
+/// let events = MockEvents::new();
+/// events.register(&mut router);
+/// router.install();
+///
+/// let mut stream = listen::<u32>("rust-event", EventOptions::new()).await?;
+///
+/// mock_emit("rust-event", &42)?;
+///
+/// assert_eq!(stream.next().await.unwrap().payload, 42);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MockEvents(Rc<RefCell<EventHub>>);
+
+impl MockEvents {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the event commands with the given router and makes this the event bus used by [`mock_emit`] and [`mock_emit_to`].
+    pub fn register(&self, router: &mut MockRouter) {
+        let hub = self.clone();
+        router.add_raw_command("plugin:event|listen", move |payload| {
+            let event = get(&payload, "event")?
+                .as_string()
+                .ok_or_else(|| JsValue::from_str("event name must be a string"))?;
+            let handler = get(&payload, "handler")?
+                .as_f64()
+                .ok_or_else(|| JsValue::from_str("handler must be a callback id"))?;
+            let window_label = target_label(&payload)?;
+
+            Ok(hub.listen(event, window_label, handler as u32).into())
+        });
+
+        let hub = self.clone();
+        router.add_command("plugin:event|unlisten", move |args: UnlistenArgs| {
+            hub.0
+                .borrow_mut()
+                .listeners
+                .retain(|listener| listener.id != args.event_id);
+
+            Ok::<_, ()>(())
+        });
+
+        let hub = self.clone();
+        router.add_raw_command("plugin:event|emit", move |payload| {
+            let event = get(&payload, "event")?
+                .as_string()
+                .ok_or_else(|| JsValue::from_str("event name must be a string"))?;
+            let window_label = target_label(&payload)?;
+            let payload = get(&payload, "payload")?;
+
+            hub.0.borrow_mut().emitted.push(MockEvent {
+                event: event.clone(),
+                payload: payload.clone(),
+                window_label: window_label.clone(),
+            });
+
+            // deliver once this handler returned, listeners may call back into the IPC (e.g. `once` unlistens)
+            let hub = hub.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                hub.deliver(&event, payload, window_label);
+            });
+
+            Ok(JsValue::UNDEFINED)
+        });
+
+        CURRENT_EVENTS.with(|current| *current.borrow_mut() = Some(self.clone()));
+    }
+
+    /// Returns the events emitted by the frontend in the order they were emitted.
+    pub fn emitted(&self) -> Vec<MockEvent> {
+        self.0.borrow().emitted.clone()
+    }
+
+    /// Returns the number of listeners currently registered for the given event.
+    pub fn listener_count(&self, event: &str) -> usize {
+        self.0
+            .borrow()
+            .listeners
+            .iter()
+            .filter(|listener| listener.event == event)
+            .count()
+    }
+
+    /// Simulates the backend emitting an event to all windows.
+    pub fn emit<T: Serialize>(&self, event: &str, payload: &T) -> crate::Result<()> {
+        self.deliver(event, to_value(payload)?, None);
+
+        Ok(())
+    }
+
+    /// Simulates the backend emitting an event to the window with the given label.
+    pub fn emit_to<T: Serialize>(
+        &self,
+        window_label: &str,
+        event: &str,
+        payload: &T,
+    ) -> crate::Result<()> {
+        self.deliver(event, to_value(payload)?, Some(window_label.to_string()));
+
+        Ok(())
+    }
+
+    fn listen(&self, event: String, window_label: Option<String>, handler: u32) -> u32 {
+        let mut hub = self.0.borrow_mut();
+
+        let id = hub.next_id;
+        hub.next_id += 1;
+        hub.listeners.push(MockListener {
+            id,
+            event,
+            window_label,
+            handler,
+        });
+
+        id
+    }
+
+    fn deliver(&self, event: &str, payload: JsValue, window_label: Option<String>) {
+        // collect the handlers first, they may unlisten while being called
+        let listeners: Vec<(u32, u32)> = self
+            .0
+            .borrow()
+            .listeners
+            .iter()
+            .filter(|listener| listener.event == event)
+            .filter(|listener| match (&listener.window_label, &window_label) {
+                (Some(listening), Some(target)) => listening == target,
+                _ => true,
+            })
+            .map(|listener| (listener.id, listener.handler))
+            .collect();
+
+        for (id, handler) in listeners {
+            let data = Object::new();
+            let _ = Reflect::set(&data, &"event".into(), &event.into());
+            let _ = Reflect::set(&data, &"id".into(), &id.into());
+            let _ = Reflect::set(&data, &"payload".into(), &payload);
+            let _ = Reflect::set(
+                &data,
+                &"windowLabel".into(),
+                &window_label
+                    .as_deref()
+                    .map_or(JsValue::NULL, JsValue::from_str),
+            );
+
+            let callback = Reflect::get(&js_sys::global(), &format!("_{}", handler).into())
+                .ok()
+                .and_then(|callback| callback.dyn_into::<Function>().ok());
+
+            match callback {
+                Some(callback) => {
+                    if let Err(e) = callback.call1(&JsValue::NULL, &data) {
+                        log::error!("Mocked event handler for `{}` failed: {:?}", event, e);
+                    }
+                }
+                None => log::warn!("Mocked event handler for `{}` is gone", event),
+            }
+        }
+    }
+}
+
+/// Simulates the backend emitting an event to all windows.
+///
+/// The event is delivered through the [`MockEvents`] registered last.
+pub fn mock_emit<T: Serialize>(event: &str, payload: &T) -> crate::Result<()> {
+    current_events()?.emit(event, payload)
+}
+
+/// Simulates the backend emitting an event to the window with the given label.
+///
+/// The event is delivered through the [`MockEvents`] registered last.
+pub fn mock_emit_to<T: Serialize>(
+    window_label: &str,
+    event: &str,
+    payload: &T,
+) -> crate::Result<()> {
+    current_events()?.emit_to(window_label, event, payload)
+}
+
+/// The label of the window an event command targets, the window API sends it as `windowLabel` instead of `target`.
+fn target_label(payload: &JsValue) -> Result<Option<String>, JsValue> {
+    match get(payload, "target")?.as_string() {
+        Some(label) => Ok(Some(label)),
+        None => Ok(get(payload, "windowLabel")?.as_string()),
+    }
+}
+
+fn current_events() -> crate::Result<MockEvents> {
+    CURRENT_EVENTS
+        .with(|current| current.borrow().clone())
+        .ok_or_else(|| crate::Error::Binding("no mocked event bus registered".to_string()))
+}
+
+#[cfg(feature = "fs")]
+#[derive(Debug, Clone)]
+enum FsNode {
+    File {
+        contents: Vec<u8>,
+        created_at: f64,
+        modified_at: f64,
+    },
+    Dir {
+        created_at: f64,
+    },
+}
+
+#[cfg(feature = "fs")]
+#[derive(Debug, Default)]
+struct FsTree {
+    /// The nodes of each base directory, keyed by their normalized path relative to it.
+    /// Paths without a base directory are stored under `None`.
+    nodes: HashMap<Option<BaseDirectory>, BTreeMap<PathBuf, FsNode>>,
+    /// The files opened through `open`, keyed by their resource id.
+    handles: HashMap<u32, OpenFile>,
+    /// The paths watched through `watch`, keyed by their resource id.
+    watchers: HashMap<u32, FsWatcher>,
+    /// The changes not yet reported to the watchers.
+    changes: Vec<FsChange>,
+    next_rid: u32,
+}
+
+#[cfg(feature = "fs")]
+#[derive(Debug)]
+struct FsWatcher {
+    dir: Option<BaseDirectory>,
+    paths: Vec<PathBuf>,
+    recursive: bool,
+    channel: u32,
+}
+
+#[cfg(feature = "fs")]
+#[derive(Debug, Clone, Serialize)]
+struct FsChange {
+    #[serde(skip)]
+    dir: Option<BaseDirectory>,
+    #[serde(rename = "type")]
+    kind: FsChangeKind,
+    paths: Vec<PathBuf>,
+}
+
+/// The kinds of changes reported by the backend, mirroring the events of the `notify` crate.
+#[cfg(feature = "fs")]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum FsChangeKind {
+    Create {
+        kind: &'static str,
+    },
+    Modify {
+        kind: &'static str,
+        mode: &'static str,
+    },
+    Remove {
+        kind: &'static str,
+    },
+}
+
+#[cfg(feature = "fs")]
+impl FsChangeKind {
+    const CREATE_FILE: Self = Self::Create { kind: "file" };
+    const CREATE_FOLDER: Self = Self::Create { kind: "folder" };
+    const MODIFY_DATA: Self = Self::Modify {
+        kind: "data",
+        mode: "content",
+    };
+    const RENAME: Self = Self::Modify {
+        kind: "rename",
+        mode: "both",
+    };
+    const REMOVE_FILE: Self = Self::Remove { kind: "file" };
+
+    fn remove(node: &FsNode) -> Self {
+        match node {
+            FsNode::File { .. } => Self::REMOVE_FILE,
+            FsNode::Dir { .. } => Self::Remove { kind: "folder" },
+        }
+    }
+}
+
+#[cfg(feature = "fs")]
+impl FsWatcher {
+    fn matches(&self, change: &FsChange) -> bool {
+        self.dir == change.dir
+            && change.paths.iter().any(|changed| {
+                self.paths.iter().any(|watched| {
+                    changed == watched
+                        || changed.parent() == Some(watched)
+                        || (self.recursive && changed.starts_with(watched))
+                })
+            })
+    }
+}
+
+#[cfg(feature = "fs")]
+#[derive(Debug)]
+struct OpenFile {
+    dir: Option<BaseDirectory>,
+    path: PathBuf,
+    position: usize,
+    read: bool,
+    write: bool,
+    append: bool,
+}
+
+#[cfg(feature = "fs")]
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct FsOpenOptions {
+    dir: Option<BaseDirectory>,
+    read: bool,
+    write: bool,
+    append: bool,
+    create: bool,
+    create_new: bool,
+    truncate: bool,
+}
+
+#[cfg(feature = "fs")]
+#[derive(Deserialize)]
+struct FsOpenArgs {
+    path: String,
+    #[serde(default)]
+    options: FsOpenOptions,
+}
+
+#[cfg(feature = "fs")]
+#[derive(Deserialize)]
+struct FsReadArgs {
+    rid: u32,
+    len: usize,
+}
+
+#[cfg(feature = "fs")]
+#[derive(Deserialize)]
+struct FsWriteChunkArgs {
+    rid: u32,
+    data: Vec<u8>,
+}
+
+#[cfg(feature = "fs")]
+#[derive(Deserialize)]
+struct FsSeekArgs {
+    rid: u32,
+    offset: f64,
+    whence: u32,
+}
+
+#[cfg(feature = "fs")]
+#[derive(Deserialize)]
+struct FsCloseArgs {
+    rid: u32,
+}
+
+#[cfg(feature = "fs")]
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FsWatchArgs {
+    paths: Vec<String>,
+    options: FsMockOptions,
+    on_event: FsChannel,
+}
+
+/// A `Channel` passed to a command, only its callback id is of interest.
+#[cfg(feature = "fs")]
+#[derive(Deserialize)]
+struct FsChannel {
+    id: u32,
+}
+
+#[cfg(feature = "fs")]
+#[derive(Deserialize)]
+struct FsMockOptions {
+    dir: Option<BaseDirectory>,
+    #[serde(default)]
+    recursive: Option<bool>,
+}
+
+#[cfg(feature = "fs")]
+#[derive(Deserialize)]
+struct FsPathArgs {
+    path: String,
+    options: Option<FsMockOptions>,
+}
+
+#[cfg(feature = "fs")]
+#[derive(Deserialize)]
+struct FsWriteArgs {
+    path: String,
+    contents: Vec<u8>,
+    options: Option<FsMockOptions>,
+}
+
+#[cfg(feature = "fs")]
+#[derive(Deserialize)]
+struct FsCopyArgs {
+    source: String,
+    destination: String,
+    options: Option<FsMockOptions>,
+}
+
+#[cfg(feature = "fs")]
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FsRenameArgs {
+    old_path: String,
+    new_path: String,
+    options: Option<FsMockOptions>,
+}
+
+#[cfg(feature = "fs")]
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FsMockEntry {
+    path: PathBuf,
+    name: Option<String>,
+    children: Option<Vec<FsMockEntry>>,
+    is_directory: bool,
+    is_file: bool,
+    is_symlink: bool,
+}
+
+#[cfg(feature = "fs")]
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FsMockMetadata {
+    accessed_at_ms: i64,
+    created_at_ms: i64,
+    modified_at_ms: i64,
+    is_dir: bool,
+    is_file: bool,
+    is_symlink: bool,
+    size: u64,
+    permissions: FsMockPermissions,
+}
+
+#[cfg(feature = "fs")]
+#[derive(Serialize)]
+struct FsMockPermissions {
+    readonly: bool,
+    mode: Option<u32>,
+}
+
+#[cfg(feature = "fs")]
+impl FsMockOptions {
+    fn dir(options: &Option<Self>) -> Option<BaseDirectory> {
+        options.as_ref().and_then(|options| options.dir)
+    }
+
+    fn recursive(options: &Option<Self>) -> bool {
+        options
+            .as_ref()
+            .and_then(|options| options.recursive)
+            .unwrap_or(false)
+    }
+}
+
+/// An in-memory file system answering the commands of the [`fs`](crate::fs) module.
+///
+/// Files and directories are kept in a separate tree for each [`BaseDirectory`], the base directories themselves always exist.
+/// Failing operations are rejected with the same messages the operating system reports, e.g. `No such file or directory (os error 2)`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tauri_sys::{fs::{self, BaseDirectory, ScopedPath}, mocks::{MockFs, MockRouter}};
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut router = MockRouter::new();
+/// let mock_fs = MockFs::new();
+/// mock_fs.add_file("settings.json", BaseDirectory::AppConfig, "{}");
+/// mock_fs.register(&mut router);
+/// router.install();
+///
+/// let settings = fs::read_text_file(&ScopedPath::new(BaseDirectory::AppConfig, "settings.json")?).await?;
+/// assert_eq!(settings, "{}");
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "fs")]
+#[derive(Debug, Clone, Default)]
+pub struct MockFs(Rc<RefCell<FsTree>>);
+
+#[cfg(feature = "fs")]
+impl MockFs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a file, creating all missing parent directories.
+    ///
+    /// Like every other change, this is reported to the paths watched through [`fs::watch`](crate::fs::watch).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the path is not a valid relative path, or if one of its parents is a file.
+    pub fn add_file(
+        &self,
+        path: impl AsRef<Path>,
+        dir: BaseDirectory,
+        contents: impl Into<Vec<u8>>,
+    ) -> &Self {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            self.add_dir(parent, dir);
+        }
+
+        self.0
+            .borrow_mut()
+            .write(Some(dir), &path.to_string_lossy(), contents.into())
+            .unwrap();
+        notify_watchers(&self.0);
+        self
+    }
+
+    /// Adds a directory, creating all missing parent directories.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the path is not a valid relative path, or if one of its parents is a file.
+    pub fn add_dir(&self, path: impl AsRef<Path>, dir: BaseDirectory) -> &Self {
+        self.0
+            .borrow_mut()
+            .create_dir(Some(dir), &path.as_ref().to_string_lossy(), true)
+            .unwrap();
+        notify_watchers(&self.0);
+        self
+    }
+
+    /// Returns the contents of a file, `None` if there is no file at the path.
+    pub fn read(&self, path: impl AsRef<Path>, dir: BaseDirectory) -> Option<Vec<u8>> {
+        self.0
+            .borrow()
+            .read(Some(dir), &path.as_ref().to_string_lossy())
+            .ok()
+    }
+
+    /// Checks if a file or directory exists at the path.
+    pub fn exists(&self, path: impl AsRef<Path>, dir: BaseDirectory) -> bool {
+        self.0
+            .borrow()
+            .exists(Some(dir), &path.as_ref().to_string_lossy())
+    }
+
+    /// Registers the commands of the `fs` module with the given router.
+    pub fn register(&self, router: &mut MockRouter) {
+        let fs = self.0.clone();
+        router.add_command("plugin:fs|read_file", move |args: FsPathArgs| {
+            let dir = FsMockOptions::dir(&args.options);
+            fs.borrow().read(dir, &args.path)
+        });
+
+        let fs = self.0.clone();
+        router.add_command("plugin:fs|read_text_file", move |args: FsPathArgs| {
+            let dir = FsMockOptions::dir(&args.options);
+            let contents = fs.borrow().read(dir, &args.path)?;
+
+            String::from_utf8(contents)
+                .map_err(|_| "stream did not contain valid UTF-8".to_string())
+        });
+
+        let fs = self.0.clone();
+        router.add_command("plugin:fs|write_file", move |args: FsWriteArgs| {
+            let dir = FsMockOptions::dir(&args.options);
+            let result = fs.borrow_mut().write(dir, &args.path, args.contents);
+            notify_watchers(&fs);
+            result
+        });
+
+        let fs = self.0.clone();
+        router.add_command("plugin:fs|read_dir", move |args: FsPathArgs| {
+            let dir = FsMockOptions::dir(&args.options);
+            let recursive = FsMockOptions::recursive(&args.options);
+            fs.borrow().read_dir(dir, &args.path, recursive)
+        });
+
+        let fs = self.0.clone();
+        router.add_command("plugin:fs|create_dir", move |args: FsPathArgs| {
+            let dir = FsMockOptions::dir(&args.options);
+            let recursive = FsMockOptions::recursive(&args.options);
+            let result = fs.borrow_mut().create_dir(dir, &args.path, recursive);
+            notify_watchers(&fs);
+            result
+        });
+
+        let fs = self.0.clone();
+        router.add_command("plugin:fs|remove_dir", move |args: FsPathArgs| {
+            let dir = FsMockOptions::dir(&args.options);
+            let recursive = FsMockOptions::recursive(&args.options);
+            let result = fs.borrow_mut().remove_dir(dir, &args.path, recursive);
+            notify_watchers(&fs);
+            result
+        });
+
+        let fs = self.0.clone();
+        router.add_command("plugin:fs|remove_file", move |args: FsPathArgs| {
+            let dir = FsMockOptions::dir(&args.options);
+            let result = fs.borrow_mut().remove_file(dir, &args.path);
+            notify_watchers(&fs);
+            result
+        });
+
+        let fs = self.0.clone();
+        router.add_command("plugin:fs|copy_file", move |args: FsCopyArgs| {
+            let dir = FsMockOptions::dir(&args.options);
+            let result = fs
+                .borrow_mut()
+                .copy_file(dir, &args.source, &args.destination);
+            notify_watchers(&fs);
+            result
+        });
+
+        let fs = self.0.clone();
+        router.add_command("plugin:fs|rename_file", move |args: FsRenameArgs| {
+            let dir = FsMockOptions::dir(&args.options);
+            let result = fs.borrow_mut().rename(dir, &args.old_path, &args.new_path);
+            notify_watchers(&fs);
+            result
+        });
+
+        let fs = self.0.clone();
+        router.add_command("plugin:fs|exists", move |args: FsPathArgs| {
+            let dir = FsMockOptions::dir(&args.options);
+            Ok::<_, String>(fs.borrow().exists(dir, &args.path))
+        });
+
+        let fs = self.0.clone();
+        router.add_command("plugin:fs|metadata", move |args: FsPathArgs| {
+            let dir = FsMockOptions::dir(&args.options);
+            fs.borrow().metadata(dir, &args.path)
+        });
+
+        let fs = self.0.clone();
+        router.add_command("plugin:fs|open", move |args: FsOpenArgs| {
+            let result = fs.borrow_mut().open(&args.path, args.options);
+            notify_watchers(&fs);
+            result
+        });
+
+        let fs = self.0.clone();
+        router.add_command("plugin:fs|read", move |args: FsReadArgs| {
+            fs.borrow_mut().read_chunk(args.rid, args.len)
+        });
+
+        let fs = self.0.clone();
+        router.add_command("plugin:fs|write", move |args: FsWriteChunkArgs| {
+            let result = fs.borrow_mut().write_chunk(args.rid, &args.data);
+            notify_watchers(&fs);
+            result
+        });
+
+        let fs = self.0.clone();
+        router.add_command("plugin:fs|seek", move |args: FsSeekArgs| {
+            fs.borrow_mut()
+                .seek(args.rid, args.offset as i64, args.whence)
+        });
+
+        let fs = self.0.clone();
+        router.add_command("plugin:resources|close", move |args: FsCloseArgs| match fs
+            .borrow_mut()
+            .handles
+            .remove(&args.rid)
+        {
+            Some(_) => Ok(()),
+            None => Err("resource not found".to_string()),
+        });
+
+        self.register_watch(router);
+    }
+
+    /// Registers the commands used by [`fs::watch`](crate::fs::watch).
+    ///
+    /// Changes are reported to the watchers right away, debouncing is not simulated.
+    fn register_watch(&self, router: &mut MockRouter) {
+        let fs = self.0.clone();
+        router.add_command("plugin:fs|watch", move |args: FsWatchArgs| {
+            let dir = args.options.dir;
+            let paths = args
+                .paths
+                .iter()
+                .map(|raw| FsTree::normalize(dir, raw))
+                .collect::<Result<_, _>>()?;
+
+            let mut fs = fs.borrow_mut();
+            let rid = fs.next_rid;
+            fs.next_rid += 1;
+            fs.watchers.insert(
+                rid,
+                FsWatcher {
+                    dir,
+                    paths,
+                    recursive: args.options.recursive.unwrap_or(false),
+                    channel: args.on_event.id,
+                },
+            );
+
+            Ok::<_, String>(rid)
+        });
+
+        let fs = self.0.clone();
+        router.add_command("plugin:fs|unwatch", move |args: FsCloseArgs| {
+            match fs.borrow_mut().watchers.remove(&args.rid) {
+                Some(_) => Ok(()),
+                None => Err("resource not found".to_string()),
+            }
+        });
+    }
+
+    /// Returns the number of paths currently watched.
+    pub fn watchers(&self) -> usize {
+        self.0.borrow().watchers.len()
+    }
+
+    /// Returns the number of files currently opened through the streaming APIs.
+    pub fn open_files(&self) -> usize {
+        self.0.borrow().handles.len()
+    }
+}
+
+#[cfg(feature = "fs")]
+impl FsTree {
+    /// Records a change to be reported to the watchers, see [`notify_watchers`].
+    fn record(&mut self, dir: Option<BaseDirectory>, kind: FsChangeKind, paths: Vec<PathBuf>) {
+        if !self.watchers.is_empty() {
+            self.changes.push(FsChange { dir, kind, paths });
+        }
+    }
+
+    /// Normalizes a path, rejecting paths escaping the base directory.
+    fn normalize(dir: Option<BaseDirectory>, path: &str) -> Result<PathBuf, String> {
+        let mut normalized = PathBuf::new();
+
+        for component in Path::new(path).components() {
+            match component {
+                Component::Normal(name) => normalized.push(name),
+                Component::CurDir => {}
+                Component::RootDir if dir.is_none() => {}
+                _ => return Err(format!("path not allowed: {}", path)),
+            }
+        }
+
+        Ok(normalized)
+    }
+
+    fn nodes(&self, dir: Option<BaseDirectory>) -> Option<&BTreeMap<PathBuf, FsNode>> {
+        self.nodes.get(&dir)
+    }
+
+    fn get(&self, dir: Option<BaseDirectory>, path: &Path) -> Option<FsNode> {
+        if path.as_os_str().is_empty() {
+            return Some(FsNode::Dir { created_at: 0.0 });
+        }
+
+        self.nodes(dir).and_then(|nodes| nodes.get(path)).cloned()
+    }
+
+    /// Checks that the parent of the path is an existing directory.
+    fn check_parent(
+        &self,
+        dir: Option<BaseDirectory>,
+        path: &Path,
+        raw: &str,
+    ) -> Result<(), String> {
+        match path.parent().map(|parent| self.get(dir, parent)) {
+            Some(Some(FsNode::Dir { .. })) | None => Ok(()),
+            Some(Some(FsNode::File { .. })) => Err(os_error(raw, 20)),
+            Some(None) => Err(os_error(raw, 2)),
+        }
+    }
+
+    fn children(&self, dir: Option<BaseDirectory>, path: &Path) -> Vec<PathBuf> {
+        self.nodes(dir)
+            .map(|nodes| {
+                nodes
+                    .keys()
+                    .filter(|child| child.parent() == Some(path))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn exists(&self, dir: Option<BaseDirectory>, path: &str) -> bool {
+        Self::normalize(dir, path)
+            .map(|path| self.get(dir, &path).is_some())
+            .unwrap_or(false)
+    }
+
+    fn read(&self, dir: Option<BaseDirectory>, raw: &str) -> Result<Vec<u8>, String> {
+        let path = Self::normalize(dir, raw)?;
+
+        match self.get(dir, &path) {
+            Some(FsNode::File { contents, .. }) => Ok(contents),
+            Some(FsNode::Dir { .. }) => Err(os_error(raw, 21)),
+            None => Err(os_error(raw, 2)),
+        }
+    }
+
+    fn write(
+        &mut self,
+        dir: Option<BaseDirectory>,
+        raw: &str,
+        contents: Vec<u8>,
+    ) -> Result<(), String> {
+        let path = Self::normalize(dir, raw)?;
+        self.check_parent(dir, &path, raw)?;
+
+        let now = js_sys::Date::now();
+        let (created_at, kind) = match self.get(dir, &path) {
+            Some(FsNode::Dir { .. }) => return Err(os_error(raw, 21)),
+            Some(FsNode::File { created_at, .. }) => (created_at, FsChangeKind::MODIFY_DATA),
+            None => (now, FsChangeKind::CREATE_FILE),
+        };
+        self.record(dir, kind, vec![path.clone()]);
+
+        self.nodes.entry(dir).or_default().insert(
+            path,
+            FsNode::File {
+                contents,
+                created_at,
+                modified_at: now,
+            },
+        );
+
+        Ok(())
+    }
+
+    fn read_dir(
+        &self,
+        dir: Option<BaseDirectory>,
+        raw: &str,
+        recursive: bool,
+    ) -> Result<Vec<FsMockEntry>, String> {
+        let path = Self::normalize(dir, raw)?;
+
+        match self.get(dir, &path) {
+            Some(FsNode::Dir { .. }) => Ok(self.entries(dir, &path, recursive)),
+            Some(FsNode::File { .. }) => Err(os_error(raw, 20)),
+            None => Err(os_error(raw, 2)),
+        }
+    }
+
+    fn entries(
+        &self,
+        dir: Option<BaseDirectory>,
+        path: &Path,
+        recursive: bool,
+    ) -> Vec<FsMockEntry> {
+        self.children(dir, path)
+            .into_iter()
+            .map(|child| {
+                let is_directory = matches!(self.get(dir, &child), Some(FsNode::Dir { .. }));
+                let children = if is_directory && recursive {
+                    Some(self.entries(dir, &child, recursive))
+                } else {
+                    None
+                };
+
+                FsMockEntry {
+                    name: child
+                        .file_name()
+                        .map(|name| name.to_string_lossy().into_owned()),
+                    path: child,
+                    children,
+                    is_directory,
+                    is_file: !is_directory,
+                    is_symlink: false,
+                }
+            })
+            .collect()
+    }
+
+    fn create_dir(
+        &mut self,
+        dir: Option<BaseDirectory>,
+        raw: &str,
+        recursive: bool,
+    ) -> Result<(), String> {
+        let path = Self::normalize(dir, raw)?;
+
+        if recursive {
+            let mut current = PathBuf::new();
+            for component in path.components() {
+                current.push(component);
+
+                match self.get(dir, &current) {
+                    Some(FsNode::Dir { .. }) => {}
+                    Some(FsNode::File { .. }) => return Err(os_error(raw, 17)),
+                    None => {
+                        self.record(dir, FsChangeKind::CREATE_FOLDER, vec![current.clone()]);
+                        self.nodes.entry(dir).or_default().insert(
+                            current.clone(),
+                            FsNode::Dir {
+                                created_at: js_sys::Date::now(),
+                            },
+                        );
+                    }
+                }
+            }
+
+            return Ok(());
+        }
+
+        if self.get(dir, &path).is_some() {
+            return Err(os_error(raw, 17));
+        }
+        self.check_parent(dir, &path, raw)?;
+
+        self.record(dir, FsChangeKind::CREATE_FOLDER, vec![path.clone()]);
+        self.nodes.entry(dir).or_default().insert(
+            path,
+            FsNode::Dir {
+                created_at: js_sys::Date::now(),
+            },
+        );
+
+        Ok(())
+    }
+
+    fn remove_dir(
+        &mut self,
+        dir: Option<BaseDirectory>,
+        raw: &str,
+        recursive: bool,
+    ) -> Result<(), String> {
+        let path = Self::normalize(dir, raw)?;
+
+        match self.get(dir, &path) {
+            Some(FsNode::Dir { .. }) => {}
+            Some(FsNode::File { .. }) => return Err(os_error(raw, 20)),
+            None => return Err(os_error(raw, 2)),
+        }
+
+        if !recursive && !self.children(dir, &path).is_empty() {
+            return Err(os_error(raw, 39));
+        }
+
+        let removed: Vec<(PathBuf, FsChangeKind)> = self
+            .nodes(dir)
+            .map(|nodes| {
+                nodes
+                    .iter()
+                    .filter(|(node, _)| node.starts_with(&path))
+                    .map(|(node, kind)| (node.clone(), FsChangeKind::remove(kind)))
+                    .collect()
+            })
+            .unwrap_or_default();
+        // report the contents before the directory itself
+        for (node, kind) in removed.into_iter().rev() {
+            self.record(dir, kind, vec![node]);
+        }
+
+        if let Some(nodes) = self.nodes.get_mut(&dir) {
+            nodes.retain(|node, _| !node.starts_with(&path));
+        }
+
+        Ok(())
+    }
+
+    fn remove_file(&mut self, dir: Option<BaseDirectory>, raw: &str) -> Result<(), String> {
+        let path = Self::normalize(dir, raw)?;
+
+        match self.get(dir, &path) {
+            Some(FsNode::File { .. }) => {
+                self.record(dir, FsChangeKind::REMOVE_FILE, vec![path.clone()]);
+                if let Some(nodes) = self.nodes.get_mut(&dir) {
+                    nodes.remove(&path);
+                }
+                Ok(())
+            }
+            Some(FsNode::Dir { .. }) => Err(os_error(raw, 21)),
+            None => Err(os_error(raw, 2)),
+        }
+    }
+
+    fn copy_file(
+        &mut self,
+        dir: Option<BaseDirectory>,
+        source: &str,
+        destination: &str,
+    ) -> Result<(), String> {
+        let source_path = Self::normalize(dir, source)?;
+
+        match self.get(dir, &source_path) {
+            Some(FsNode::File { contents, .. }) => self.write(dir, destination, contents),
+            Some(FsNode::Dir { .. }) => Err(
+                "the source path is neither a regular file nor a symlink to a regular file"
+                    .to_string(),
+            ),
+            None => Err(os_error(source, 2)),
+        }
+    }
+
+    fn rename(&mut self, dir: Option<BaseDirectory>, old: &str, new: &str) -> Result<(), String> {
+        let old_path = Self::normalize(dir, old)?;
+        let new_path = Self::normalize(dir, new)?;
+
+        let node = match self.get(dir, &old_path) {
+            Some(node) => node,
+            None => return Err(os_error(old, 2)),
+        };
+        self.check_parent(dir, &new_path, new)?;
+
+        match (&node, self.get(dir, &new_path)) {
+            (FsNode::File { .. }, Some(FsNode::Dir { .. })) => return Err(os_error(new, 21)),
+            (FsNode::Dir { .. }, Some(FsNode::File { .. })) => return Err(os_error(new, 20)),
+            (FsNode::Dir { .. }, Some(FsNode::Dir { .. }))
+                if !self.children(dir, &new_path).is_empty() =>
+            {
+                return Err(os_error(new, 39))
+            }
+            _ => {}
+        }
+
+        if new_path.starts_with(&old_path) && new_path != old_path {
+            return Err(format!("{}: Invalid argument (os error 22)", new));
+        }
+
+        self.record(
+            dir,
+            FsChangeKind::RENAME,
+            vec![old_path.clone(), new_path.clone()],
+        );
+
+        let nodes = self.nodes.entry(dir).or_default();
+        let moved: Vec<(PathBuf, FsNode)> = nodes
+            .iter()
+            .filter(|(path, _)| path.starts_with(&old_path))
+            .map(|(path, node)| (path.clone(), node.clone()))
+            .collect();
+
+        nodes.retain(|path, _| !path.starts_with(&old_path) && path != &new_path);
+        for (path, node) in moved {
+            let relative = path.strip_prefix(&old_path).unwrap_or(Path::new(""));
+            nodes.insert(new_path.join(relative), node);
+        }
+
+        Ok(())
+    }
+
+    fn metadata(&self, dir: Option<BaseDirectory>, raw: &str) -> Result<FsMockMetadata, String> {
+        let path = Self::normalize(dir, raw)?;
+
+        let (created_at, modified_at, size, is_dir) = match self.get(dir, &path) {
+            Some(FsNode::File {
+                contents,
+                created_at,
+                modified_at,
+            }) => (created_at, modified_at, contents.len() as u64, false),
+            Some(FsNode::Dir { created_at }) => (created_at, created_at, 0, true),
+            None => return Err(os_error(raw, 2)),
+        };
+
+        Ok(FsMockMetadata {
+            accessed_at_ms: modified_at as i64,
+            created_at_ms: created_at as i64,
+            modified_at_ms: modified_at as i64,
+            is_dir,
+            is_file: !is_dir,
+            is_symlink: false,
+            size,
+            permissions: FsMockPermissions {
+                readonly: false,
+                mode: None,
+            },
+        })
+    }
+}
+
+#[cfg(feature = "fs")]
+impl FsTree {
+    fn open(&mut self, raw: &str, options: FsOpenOptions) -> Result<u32, String> {
+        let dir = options.dir;
+        let path = Self::normalize(dir, raw)?;
+        let writable = options.write || options.append;
+
+        match self.get(dir, &path) {
+            Some(FsNode::Dir { .. }) => return Err(os_error(raw, 21)),
+            Some(FsNode::File { .. }) if options.create_new => return Err(os_error(raw, 17)),
+            Some(FsNode::File { .. }) => {
+                if options.truncate && writable {
+                    self.write(dir, raw, Vec::new())?;
+                }
+            }
+            None if writable && (options.create || options.create_new) => {
+                self.write(dir, raw, Vec::new())?;
+            }
+            None => return Err(os_error(raw, 2)),
+        }
+
+        let rid = self.next_rid;
+        self.next_rid += 1;
+        self.handles.insert(
+            rid,
+            OpenFile {
+                dir,
+                path,
+                position: 0,
+                read: options.read || !writable,
+                write: writable,
+                append: options.append,
+            },
+        );
+
+        Ok(rid)
+    }
+
+    fn handle(&mut self, rid: u32) -> Result<&mut OpenFile, String> {
+        self.handles
+            .get_mut(&rid)
+            .ok_or_else(|| "resource not found".to_string())
+    }
+
+    fn contents(
+        &mut self,
+        dir: Option<BaseDirectory>,
+        path: &Path,
+    ) -> Result<&mut Vec<u8>, String> {
+        match self
+            .nodes
+            .get_mut(&dir)
+            .and_then(|nodes| nodes.get_mut(path))
+        {
+            Some(FsNode::File {
+                contents,
+                modified_at,
+                ..
+            }) => {
+                *modified_at = js_sys::Date::now();
+                Ok(contents)
+            }
+            _ => Err(os_error(&path.to_string_lossy(), 2)),
+        }
+    }
+
+    fn read_chunk(&mut self, rid: u32, len: usize) -> Result<Vec<u8>, String> {
+        let handle = self.handle(rid)?;
+        if !handle.read {
+            return Err("Bad file descriptor (os error 9)".to_string());
+        }
+        let (dir, path, position) = (handle.dir, handle.path.clone(), handle.position);
+
+        let contents = self.contents(dir, &path)?;
+        let start = position.min(contents.len());
+        let end = (start + len).min(contents.len());
+        let chunk = contents[start..end].to_vec();
+
+        self.handle(rid)?.position = end;
+
+        Ok(chunk)
+    }
+
+    fn write_chunk(&mut self, rid: u32, data: &[u8]) -> Result<usize, String> {
+        let handle = self.handle(rid)?;
+        if !handle.write {
+            return Err("Bad file descriptor (os error 9)".to_string());
+        }
+        let (dir, path, append, position) = (
+            handle.dir,
+            handle.path.clone(),
+            handle.append,
+            handle.position,
+        );
+
+        let contents = self.contents(dir, &path)?;
+        let start = if append { contents.len() } else { position };
+        if contents.len() < start + data.len() {
+            contents.resize(start + data.len(), 0);
+        }
+        contents[start..start + data.len()].copy_from_slice(data);
+        self.record(dir, FsChangeKind::MODIFY_DATA, vec![path]);
+
+        self.handle(rid)?.position = start + data.len();
+
+        Ok(data.len())
+    }
+
+    fn seek(&mut self, rid: u32, offset: i64, whence: u32) -> Result<u64, String> {
+        let handle = self.handle(rid)?;
+        let (dir, path, position) = (handle.dir, handle.path.clone(), handle.position);
+        let len = self.contents(dir, &path)?.len();
+
+        let base = match whence {
+            0 => 0,
+            1 => position as i64,
+            2 => len as i64,
+            _ => return Err("Invalid argument (os error 22)".to_string()),
+        };
+        let position = base + offset;
+        if position < 0 {
+            return Err("Invalid argument (os error 22)".to_string());
+        }
+
+        self.handle(rid)?.position = position as usize;
+
+        Ok(position as u64)
+    }
+}
+
+/// Reports the recorded changes to the watchers whose paths they affect.
+#[cfg(feature = "fs")]
+fn notify_watchers(fs: &RefCell<FsTree>) {
+    // collect the deliveries first, the handlers are called without holding the borrow
+    let deliveries: Vec<(u32, FsChange)> = {
+        let mut fs = fs.borrow_mut();
+        let changes = std::mem::take(&mut fs.changes);
+
+        changes
+            .into_iter()
+            .flat_map(|change| {
+                fs.watchers
+                    .values()
+                    .filter(|watcher| watcher.matches(&change))
+                    .map(|watcher| (watcher.channel, change.clone()))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    };
+
+    for (channel, change) in deliveries {
+        let callback = Reflect::get(&js_sys::global(), &format!("_{}", channel).into())
+            .ok()
+            .and_then(|callback| callback.dyn_into::<Function>().ok());
+
+        match (callback, to_value(&change)) {
+            (Some(callback), Ok(payload)) => {
+                if let Err(e) = callback.call1(&JsValue::NULL, &payload) {
+                    log::error!("Mocked watch handler failed: {:?}", e);
+                }
+            }
+            (None, _) => log::warn!("Mocked watch handler is gone"),
+            (_, Err(e)) => log::error!("Failed to serialize mocked watch event: {:?}", e),
+        }
+    }
+}
+
+/// Formats an error the way the operating system reports it.
+#[cfg(feature = "fs")]
+fn os_error(path: &str, code: i32) -> String {
+    let message = match code {
+        2 => "No such file or directory",
+        17 => "File exists",
+        20 => "Not a directory",
+        21 => "Is a directory",
+        39 => "Directory not empty",
+        _ => "Unknown error",
+    };
+
+    format!("{}: {} (os error {})", path, message, code)
+}
+
+/// The state of a window tracked by [`MockWindowManager`].
+#[cfg(feature = "window")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockWindowState {
+    pub title: String,
+    /// The inner width in physical pixels.
+    pub width: u32,
+    /// The inner height in physical pixels.
+    pub height: u32,
+    /// The horizontal position in physical pixels.
+    pub x: i32,
+    /// The vertical position in physical pixels.
+    pub y: i32,
+    pub scale_factor: f64,
+    pub theme: Theme,
+    pub maximized: bool,
+    pub minimized: bool,
+    pub visible: bool,
+    pub fullscreen: bool,
+    pub focused: bool,
+    pub decorated: bool,
+    pub resizable: bool,
+    pub maximizable: bool,
+    pub minimizable: bool,
+    pub closable: bool,
+    pub always_on_top: bool,
+}
+
+#[cfg(feature = "window")]
+impl Default for MockWindowState {
+    fn default() -> Self {
+        Self {
+            title: String::new(),
+            width: 800,
+            height: 600,
+            x: 0,
+            y: 0,
+            scale_factor: 1.0,
+            theme: Theme::Light,
+            maximized: false,
+            minimized: false,
+            visible: true,
+            fullscreen: false,
+            focused: false,
+            decorated: true,
+            resizable: true,
+            maximizable: true,
+            minimizable: true,
+            closable: true,
+            always_on_top: false,
+        }
+    }
+}
+
+#[cfg(feature = "window")]
+#[derive(Debug, Default)]
+struct WindowManagerState {
+    current: String,
+    windows: Vec<(String, MockWindowState)>,
+}
+
+#[cfg(feature = "window")]
+impl WindowManagerState {
+    fn window(&mut self, label: &str) -> Result<&mut MockWindowState, String> {
+        self.windows
+            .iter_mut()
+            .find(|(window, _)| window == label)
+            .map(|(_, state)| state)
+            .ok_or_else(|| "window not found".to_string())
+    }
+
+    fn labels(&self) -> Vec<&str> {
+        self.windows
+            .iter()
+            .map(|(label, _)| label.as_str())
+            .collect()
+    }
+}
+
+#[cfg(feature = "window")]
+#[derive(Serialize)]
+struct MockSize {
+    width: u32,
+    height: u32,
+}
+
+#[cfg(feature = "window")]
+#[derive(Serialize)]
+struct MockPosition {
+    x: i32,
+    y: i32,
+}
+
+#[cfg(feature = "window")]
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MockMonitor {
+    name: &'static str,
+    scale_factor: f64,
+    position: MockPosition,
+    size: MockSize,
+}
+
+#[cfg(feature = "window")]
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct MockWindowOptions {
+    label: String,
+    title: Option<String>,
+    x: Option<i32>,
+    y: Option<i32>,
+    width: Option<u32>,
+    height: Option<u32>,
+    theme: Option<Theme>,
+    fullscreen: bool,
+    maximized: bool,
+    visible: Option<bool>,
+    decorations: Option<bool>,
+    resizable: Option<bool>,
+    always_on_top: bool,
+}
+
+#[cfg(feature = "window")]
+#[derive(Deserialize)]
+struct MockCreateArgs {
+    options: MockWindowOptions,
+}
+
+/// A mock of the window manager, tracking the state of every window and answering the commands of the [`window`](crate::window) module.
+///
+/// Setters update the tracked state, so later getters stay consistent with them.
+/// [`set_size`](crate::window::Window::set_size) and [`set_position`](crate::window::Window::set_position)
+/// emit `tauri://resize` and `tauri://move` events to the window through the [`MockEvents`] registered last, if any.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tauri_sys::{mocks::{MockEvents, MockRouter, MockWindowManager}, window::{current_window, PhysicalSize}};
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut router = MockRouter::new();
+/// let windows = MockWindowManager::new("main");
+/// windows.add_window("settings");
+/// windows.register(&mut router);
+/// MockEvents::new().register(&mut router);
+/// router.install();
+///
+/// let window = current_window();
+/// window.set_size(PhysicalSize::new(1024, 768)).await?;
+///
+/// assert_eq!(windows.state("main").unwrap().width, 1024);
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "window")]
+#[derive(Debug, Clone)]
+pub struct MockWindowManager(Rc<RefCell<WindowManagerState>>);
+
+#[cfg(feature = "window")]
+impl MockWindowManager {
+    /// Creates a window manager with a single window, the one the frontend is running in.
+    pub fn new(current: &str) -> Self {
+        Self(Rc::new(RefCell::new(WindowManagerState {
+            current: current.to_string(),
+            windows: vec![(current.to_string(), MockWindowState::default())],
+        })))
+    }
+
+    /// Adds a window with the default state.
+    pub fn add_window(&self, label: &str) -> &Self {
+        self.set_state(label, MockWindowState::default())
+    }
+
+    /// Sets the state of a window, adding the window if it doesn't exist yet.
+    pub fn set_state(&self, label: &str, state: MockWindowState) -> &Self {
+        let mut manager = self.0.borrow_mut();
+
+        match manager.window(label) {
+            Ok(window) => *window = state,
+            Err(_) => manager.windows.push((label.to_string(), state)),
+        }
+        drop(manager);
+
+        self.mock_presence();
+        self
+    }
+
+    /// Returns the current state of a window, `None` if there is no window with the label.
+    pub fn state(&self, label: &str) -> Option<MockWindowState> {
+        self.0.borrow_mut().window(label).ok().cloned()
+    }
+
+    /// Returns the labels of all windows.
+    pub fn labels(&self) -> Vec<String> {
+        self.0
+            .borrow()
+            .labels()
+            .into_iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    /// Registers the commands of the `window` module with the given router and mocks the presence of the windows.
+    pub fn register(&self, router: &mut MockRouter) {
+        self.mock_presence();
+
+        self.add_getter(router, "scale_factor", |window| window.scale_factor);
+        self.add_getter(router, "inner_position", |window| MockPosition {
+            x: window.x,
+            y: window.y,
+        });
+        self.add_getter(router, "outer_position", |window| MockPosition {
+            x: window.x,
+            y: window.y,
+        });
+        self.add_getter(router, "inner_size", |window| MockSize {
+            width: window.width,
+            height: window.height,
+        });
+        self.add_getter(router, "outer_size", |window| MockSize {
+            width: window.width,
+            height: window.height,
+        });
+        self.add_getter(router, "is_fullscreen", |window| window.fullscreen);
+        self.add_getter(router, "is_minimized", |window| window.minimized);
+        self.add_getter(router, "is_maximized", |window| window.maximized);
+        self.add_getter(router, "is_focused", |window| window.focused);
+        self.add_getter(router, "is_decorated", |window| window.decorated);
+        self.add_getter(router, "is_resizable", |window| window.resizable);
+        self.add_getter(router, "is_maximizable", |window| window.maximizable);
+        self.add_getter(router, "is_minimizable", |window| window.minimizable);
+        self.add_getter(router, "is_closable", |window| window.closable);
+        self.add_getter(router, "is_visible", |window| window.visible);
+        self.add_getter(router, "title", |window| window.title.clone());
+        self.add_getter(router, "theme", |window| window.theme.clone());
+
+        self.add_flag(router, "set_resizable", |window, value| {
+            window.resizable = value
+        });
+        self.add_flag(router, "set_maximizable", |window, value| {
+            window.maximizable = value
+        });
+        self.add_flag(router, "set_minimizable", |window, value| {
+            window.minimizable = value
+        });
+        self.add_flag(router, "set_closable", |window, value| {
+            window.closable = value
+        });
+        self.add_flag(router, "set_decorations", |window, value| {
+            window.decorated = value
+        });
+        self.add_flag(router, "set_always_on_top", |window, value| {
+            window.always_on_top = value
+        });
+        self.add_flag(router, "set_fullscreen", |window, value| {
+            window.fullscreen = value
+        });
+
+        self.add_action(router, "set_title", |window, args| {
+            window.title = get(args, "value")
+                .ok()
+                .and_then(|value| value.as_string())
+                .unwrap_or_default();
+        });
+        self.add_action(router, "maximize", |window, _| window.maximized = true);
+        self.add_action(router, "unmaximize", |window, _| window.maximized = false);
+        self.add_action(router, "toggle_maximize", |window, _| {
+            window.maximized = !window.maximized
+        });
+        self.add_action(router, "minimize", |window, _| window.minimized = true);
+        self.add_action(router, "unminimize", |window, _| window.minimized = false);
+        self.add_action(router, "show", |window, _| window.visible = true);
+        self.add_action(router, "hide", |window, _| window.visible = false);
+        self.add_action(router, "set_focus", |window, _| window.focused = true);
+        for command in [
+            "center",
+            "request_user_attention",
+            "set_shadow",
+            "set_effects",
+            "set_content_protected",
+            "set_min_size",
+            "set_max_size",
+            "set_icon",
+            "set_skip_taskbar",
+            "set_cursor_grab",
+            "set_cursor_visible",
+            "set_cursor_icon",
+            "set_cursor_position",
+            "set_ignore_cursor_events",
+            "start_dragging",
+        ] {
+            self.add_action(router, command, |_, _| {});
+        }
+
+        let manager = self.clone();
+        router.add_raw_command("plugin:window|set_size", move |args| {
+            let label = label(&args)?;
+            let (width, height) = manager.scaled(&label, &args, "width", "height")?;
+
+            let mut state = manager.0.borrow_mut();
+            let window = state.window(&label)?;
+            window.width = width as u32;
+            window.height = height as u32;
+            drop(state);
+
+            emit_window_event(
+                &label,
+                "tauri://resize",
+                &MockSize {
+                    width: width as u32,
+                    height: height as u32,
+                },
+            );
+
+            Ok(JsValue::UNDEFINED)
+        });
+
+        let manager = self.clone();
+        router.add_raw_command("plugin:window|set_position", move |args| {
+            let label = label(&args)?;
+            let (x, y) = manager.scaled(&label, &args, "x", "y")?;
+
+            let mut state = manager.0.borrow_mut();
+            let window = state.window(&label)?;
+            window.x = x as i32;
+            window.y = y as i32;
+            drop(state);
+
+            emit_window_event(
+                &label,
+                "tauri://move",
+                &MockPosition {
+                    x: x as i32,
+                    y: y as i32,
+                },
+            );
+
+            Ok(JsValue::UNDEFINED)
+        });
+
+        let manager = self.clone();
+        router.add_raw_command("plugin:window|close", move |args| {
+            let label = label(&args)?;
+
+            let mut state = manager.0.borrow_mut();
+            state.window(&label)?;
+            state.windows.retain(|(window, _)| *window != label);
+            drop(state);
+
+            manager.mock_presence();
+            emit_window_event(&label, "tauri://destroyed", &());
+
+            Ok(JsValue::UNDEFINED)
+        });
+
+        let manager = self.clone();
+        router.add_command("plugin:window|create", move |args: MockCreateArgs| {
+            let options = args.options;
+            let defaults = MockWindowState::default();
+
+            if manager.state(&options.label).is_some() {
+                return Err(format!(
+                    "a window with label `{}` already exists",
+                    options.label
+                ));
+            }
+
+            manager.set_state(
+                &options.label,
+                MockWindowState {
+                    title: options.title.unwrap_or_default(),
+                    width: options.width.unwrap_or(defaults.width),
+                    height: options.height.unwrap_or(defaults.height),
+                    x: options.x.unwrap_or(defaults.x),
+                    y: options.y.unwrap_or(defaults.y),
+                    theme: options.theme.unwrap_or(defaults.theme.clone()),
+                    fullscreen: options.fullscreen,
+                    maximized: options.maximized,
+                    visible: options.visible.unwrap_or(defaults.visible),
+                    decorated: options.decorations.unwrap_or(defaults.decorated),
+                    resizable: options.resizable.unwrap_or(defaults.resizable),
+                    always_on_top: options.always_on_top,
+                    ..defaults
+                },
+            );
+
+            Ok(())
+        });
+
+        for command in ["current_monitor", "primary_monitor"] {
+            router.add_raw_command(&format!("plugin:window|{}", command), |_| {
+                to_value(&mock_monitor())
+            });
+        }
+        router.add_raw_command("plugin:window|available_monitors", |_| {
+            to_value(&[mock_monitor()])
+        });
+    }
+
+    fn mock_presence(&self) {
+        let state = self.0.borrow();
+        let additional: Vec<&str> = state
+            .labels()
+            .into_iter()
+            .filter(|label| *label != state.current)
+            .collect();
+
+        mock_windows(&state.current, &additional);
+    }
+
+    /// Reads a size or position argument, converting logical units to physical ones.
+    fn scaled(
+        &self,
+        label: &str,
+        args: &JsValue,
+        first: &str,
+        second: &str,
+    ) -> Result<(f64, f64), JsValue> {
+        let value = get(args, "value")?;
+        let data = get(&value, "data")?;
+        let first = get(&data, first)?.as_f64().unwrap_or_default();
+        let second = get(&data, second)?.as_f64().unwrap_or_default();
+
+        let scale_factor = match get(&value, "type")?.as_string().as_deref() {
+            Some("Logical") => self.0.borrow_mut().window(label)?.scale_factor,
+            _ => 1.0,
+        };
+
+        Ok((first * scale_factor, second * scale_factor))
+    }
+
+    fn add_getter<R, F>(&self, router: &mut MockRouter, command: &str, getter: F)
+    where
+        R: Serialize,
+        F: Fn(&MockWindowState) -> R + 'static,
+    {
+        let manager = self.clone();
+        router.add_raw_command(&format!("plugin:window|{}", command), move |args| {
+            let label = label(&args)?;
+            let value = getter(manager.0.borrow_mut().window(&label)?);
+
+            to_value(&value)
+        });
+    }
+
+    fn add_flag<F>(&self, router: &mut MockRouter, command: &str, setter: F)
+    where
+        F: Fn(&mut MockWindowState, bool) + 'static,
+    {
+        self.add_action(router, command, move |window, args| {
+            let value = get(args, "value")
+                .ok()
+                .and_then(|value| value.as_bool())
+                .unwrap_or_default();
+
+            setter(window, value)
+        });
+    }
+
+    fn add_action<F>(&self, router: &mut MockRouter, command: &str, action: F)
+    where
+        F: Fn(&mut MockWindowState, &JsValue) + 'static,
+    {
+        let manager = self.clone();
+        router.add_raw_command(&format!("plugin:window|{}", command), move |args| {
+            let label = label(&args)?;
+            action(manager.0.borrow_mut().window(&label)?, &args);
+
+            Ok(JsValue::UNDEFINED)
+        });
+    }
+}
+
+#[cfg(feature = "window")]
+fn label(args: &JsValue) -> Result<String, JsValue> {
+    get(args, "label")?
+        .as_string()
+        .ok_or_else(|| JsValue::from_str("window label must be a string"))
+}
+
+#[cfg(feature = "window")]
+fn mock_monitor() -> MockMonitor {
+    MockMonitor {
+        name: "Mock Monitor",
+        scale_factor: 1.0,
+        position: MockPosition { x: 0, y: 0 },
+        size: MockSize {
+            width: 1920,
+            height: 1080,
+        },
+    }
+}
+
+/// Emits a window event through the registered event bus, if any.
+#[cfg(feature = "window")]
+fn emit_window_event<T: Serialize>(label: &str, event: &str, payload: &T) {
+    if let Ok(events) = current_events() {
+        if let Err(e) = events.emit_to(label, event, payload) {
+            log::error!("Failed to emit mocked window event `{}`: {}", event, e);
+        }
+    }
+}
+
+/// Built-in modules send their commands as `tauri` with the module and command in the payload.
+fn route(cmd: String, payload: JsValue) -> (String, JsValue) {
+    if cmd != "tauri" {
+        return (cmd, payload);
+    }
+
+    let module = Reflect::get(&payload, &JsValue::from_str("__tauriModule"))
+        .ok()
+        .and_then(|module| module.as_string());
+    let message = Reflect::get(&payload, &JsValue::from_str("message")).ok();
+    let command = message
+        .as_ref()
+        .and_then(|message| Reflect::get(message, &JsValue::from_str("cmd")).ok())
+        .and_then(|command| command.as_string());
+
+    match (module, message, command) {
+        (Some(module), Some(message), Some(command)) => {
+            (format!("{}.{}", module, command), message)
+        }
+        _ => (cmd, payload),
+    }
+}
+
+fn get(target: &JsValue, key: &str) -> Result<JsValue, JsValue> {
+    Reflect::get(target, &JsValue::from_str(key))
+}
+
+fn to_value<T: Serialize>(value: &T) -> Result<JsValue, JsValue> {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(Into::into)
+}
+
 /// Clears mocked functions/data injected by the other functions in this module.
 /// When using a test runner that doesn't provide a fresh window object for each test, calling this function will reset tauri specific properties.
 pub fn clear_mocks() {
+    CURRENT_EVENTS.with(|current| current.borrow_mut().take());
     inner::clearMocks()
 }
 
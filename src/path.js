@@ -313,6 +313,16 @@ async function appLogDir() {
     }
   });
 }
+async function tempDir() {
+  return invokeTauriCommand({
+    __tauriModule: "Path",
+    message: {
+      cmd: "resolvePath",
+      path: "",
+      directory: 20 /* Temp */
+    }
+  });
+}
 var sep = isWindows() ? "\\" : "/";
 var delimiter = isWindows() ? ";" : ":";
 async function resolve(...paths) {
@@ -413,6 +423,7 @@ export {
   resourceDir,
   runtimeDir,
   sep,
+  tempDir,
   templateDir,
   videoDir
 };
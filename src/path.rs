@@ -1,5 +1,7 @@
 //! The path module provides utilities for working with file and directory paths.
 
+#[cfg(feature = "fs")]
+use crate::fs::{BaseDirectory, ScopedPath};
 use std::path::PathBuf;
 use wasm_bindgen::JsValue;
 
@@ -554,6 +556,89 @@ pub async fn app_log_dir() -> crate::Result<PathBuf> {
     Ok(serde_wasm_bindgen::from_value(raw)?)
 }
 
+/// Returns the path to the temporary directory.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tauri_sys::path::temp_dir;
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let temp_dir_path = temp_dir().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[inline(always)]
+pub async fn temp_dir() -> crate::Result<PathBuf> {
+    let raw = inner::tempDir().await?;
+
+    Ok(serde_wasm_bindgen::from_value(raw)?)
+}
+
+/// Returns the path to the given base directory.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tauri_sys::{fs::BaseDirectory, path::base_dir};
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let downloads = base_dir(BaseDirectory::Download).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "fs")]
+pub async fn base_dir(dir: BaseDirectory) -> crate::Result<PathBuf> {
+    match dir {
+        BaseDirectory::Audio => audio_dir().await,
+        BaseDirectory::Cache => cache_dir().await,
+        BaseDirectory::Config => config_dir().await,
+        BaseDirectory::Data => data_dir().await,
+        BaseDirectory::LocalData => local_data_dir().await,
+        BaseDirectory::Document => document_dir().await,
+        BaseDirectory::Download => download_dir().await,
+        BaseDirectory::Picture => picture_dir().await,
+        BaseDirectory::Public => public_dir().await,
+        BaseDirectory::Video => video_dir().await,
+        BaseDirectory::Resource => resource_dir().await,
+        BaseDirectory::Temp => temp_dir().await,
+        BaseDirectory::AppConfig => app_config_dir().await,
+        BaseDirectory::AppData => app_data_dir().await,
+        BaseDirectory::AppLocalData => app_local_data_dir().await,
+        BaseDirectory::AppCache => app_cache_dir().await,
+        BaseDirectory::AppLog => app_log_dir().await,
+        BaseDirectory::Desktop => desktop_dir().await,
+        BaseDirectory::Executable => executable_dir().await,
+        BaseDirectory::Font => font_dir().await,
+        BaseDirectory::Home => home_dir().await,
+        BaseDirectory::Runtime => runtime_dir().await,
+        BaseDirectory::Template => template_dir().await,
+    }
+}
+
+/// Resolves a [`ScopedPath`] to an absolute path by joining it onto its base directory.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tauri_sys::{fs::{BaseDirectory, ScopedPath}, path::resolve_scoped};
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let avatar = ScopedPath::new(BaseDirectory::AppData, "users/tauri/avatar.png")?;
+/// let path = resolve_scoped(&avatar).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "fs")]
+pub async fn resolve_scoped(path: &ScopedPath) -> crate::Result<PathBuf> {
+    let base = base_dir(path.base()).await?;
+    let Some(base) = base.to_str() else {
+        return Err(crate::Error::Utf8(base));
+    };
+
+    join([base, path.rel().as_str()]).await
+}
+
 /// Resolves a sequence of `paths` or `path` segments into an absolute path.
 ///
 /// # Example
@@ -771,6 +856,8 @@ mod inner {
         #[wasm_bindgen(catch)]
         pub async fn templateDir() -> Result<JsValue, JsValue>;
         #[wasm_bindgen(catch)]
+        pub async fn tempDir() -> Result<JsValue, JsValue>;
+        #[wasm_bindgen(catch)]
         pub async fn videoDir() -> Result<JsValue, JsValue>;
     }
 }
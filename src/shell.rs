@@ -0,0 +1,446 @@
+//! Access the system shell. Allows you to spawn child processes and manage files and URLs using their default application.
+//!
+//! The APIs must be added to tauri.allowlist.shell in tauri.conf.json:
+//! ```json
+//! {
+//!     "tauri": {
+//!         "allowlist": {
+//!             "shell": {
+//!                 "all": true, // enable all shell APIs
+//!                 "execute": true, // enable process spawn APIs
+//!                 "sidecar": true, // enable spawning sidecars
+//!                 "open": true // enable opening files/URLs using the default program
+//!             }
+//!         }
+//!     }
+//! }
+//! ```
+//! It is recommended to allowlist only the APIs you use for optimal bundle size and security.
+//!
+//! # Security
+//!
+//! This API has a scope configuration that forces you to restrict the programs and arguments that can be used.
+//! Trying to execute any API with a program not configured on the scope results in a promise rejection due to denied access.
+
+use futures::{channel::mpsc, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
+
+/// The status of a terminated child process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub struct ExitStatus {
+    /// The exit code of the process. `None` if the process was terminated by a signal on Unix.
+    pub code: Option<i32>,
+    /// If the process was terminated by a signal, represents that signal.
+    pub signal: Option<i32>,
+}
+
+impl ExitStatus {
+    /// Was termination successful? Signal termination is not considered a success, and success is defined as a zero exit status.
+    pub fn success(&self) -> bool {
+        self.code == Some(0)
+    }
+}
+
+/// The output of a finished process.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Output {
+    /// The status (exit code) of the process.
+    pub status: ExitStatus,
+    /// The data that the process wrote to stdout.
+    pub stdout: String,
+    /// The data that the process wrote to stderr.
+    pub stderr: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ExecuteOutput {
+    code: Option<i32>,
+    signal: Option<i32>,
+    stdout: String,
+    stderr: String,
+}
+
+/// Events emitted by a spawned child process.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CommandEvent {
+    /// A line the process wrote to stdout.
+    Stdout(String),
+    /// A line the process wrote to stderr.
+    Stderr(String),
+    /// The process terminated. This is the last event of a child.
+    Terminated(ExitStatus),
+    /// An error happened waiting for the process to terminate or reading its output.
+    Error(String),
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+struct SpawnOptions {
+    cwd: Option<PathBuf>,
+    /// Omitted to inherit the environment of the parent process, `null` to clear it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    env: Option<Option<HashMap<String, String>>>,
+    encoding: Option<String>,
+    sidecar: bool,
+}
+
+/// The entry point for spawning child processes.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tauri_sys::shell::Command;
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let output = Command::new("echo").add_arg("message").output().await?;
+///
+/// assert!(output.status.success());
+/// assert_eq!(output.stdout, "message");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Command {
+    program: String,
+    args: Vec<String>,
+    options: SpawnOptions,
+}
+
+impl Command {
+    /// Creates a new command for the given program.
+    ///
+    /// The program name must be configured on `tauri.conf.json > tauri > allowlist > shell > scope`.
+    pub fn new(program: impl Into<String>) -> Self {
+        Self {
+            program: program.into(),
+            args: Vec::new(),
+            options: SpawnOptions::default(),
+        }
+    }
+
+    /// Creates a new command for a sidecar binary.
+    ///
+    /// The program must be configured on `tauri.conf.json > tauri > bundle > externalBin`.
+    pub fn sidecar(program: impl Into<String>) -> Self {
+        let mut cmd = Self::new(program);
+        cmd.options.sidecar = true;
+        cmd
+    }
+
+    /// Appends an argument to the command.
+    pub fn add_arg(&mut self, arg: impl Into<String>) -> &mut Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Appends many arguments to the command.
+    pub fn add_args(&mut self, args: impl IntoIterator<Item = impl Into<String>>) -> &mut Self {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// Sets the working directory of the process.
+    pub fn set_cwd(&mut self, cwd: impl AsRef<Path>) -> &mut Self {
+        self.options.cwd = Some(cwd.as_ref().to_path_buf());
+        self
+    }
+
+    /// Adds an environment variable for the process, on top of the environment inherited from the parent process.
+    pub fn add_env(&mut self, key: impl Into<String>, value: impl Into<String>) -> &mut Self {
+        match &mut self.options.env {
+            Some(Some(env)) => {
+                env.insert(key.into(), value.into());
+            }
+            env => *env = Some(Some(HashMap::from([(key.into(), value.into())]))),
+        }
+        self
+    }
+
+    /// Starts the process with an empty environment.
+    ///
+    /// The backend can't clear the environment and set variables at the same time,
+    /// so this discards the variables added through [`Command::add_env`] and adding a variable afterwards inherits the parent environment again.
+    pub fn clear_env(&mut self) -> &mut Self {
+        self.options.env = Some(None);
+        self
+    }
+
+    /// Sets the character encoding used to decode stdout and stderr.
+    ///
+    /// Defaults to UTF-8, see the [`encoding_rs`](https://docs.rs/encoding_rs) crate for the supported labels.
+    pub fn set_encoding(&mut self, encoding: impl Into<String>) -> &mut Self {
+        self.options.encoding = Some(encoding.into());
+        self
+    }
+
+    /// Executes the command as a child process, waiting for it to finish and collecting all of its output.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tauri_sys::shell::Command;
+    ///
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let output = Command::sidecar("my-sidecar").add_args(["--verbose", "run"]).output().await?;
+    ///
+    /// println!("exited with {:?}: {}", output.status.code, output.stdout);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn output(&self) -> crate::Result<Output> {
+        let raw = self.to_js()?.execute().await?;
+        let output: ExecuteOutput = serde_wasm_bindgen::from_value(raw)?;
+
+        Ok(Output {
+            status: ExitStatus {
+                code: output.code,
+                signal: output.signal,
+            },
+            stdout: output.stdout,
+            stderr: output.stderr,
+        })
+    }
+
+    /// Executes the command as a child process, returning a handle to it.
+    ///
+    /// The returned [`Child`] is a stream of the [`CommandEvent`]s emitted by the process.
+    /// Dropping it detaches the event listeners, but does **not** kill the process.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use futures::StreamExt;
+    /// use tauri_sys::shell::{Command, CommandEvent};
+    ///
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut child = Command::sidecar("my-sidecar").spawn().await?;
+    ///
+    /// child.write_stdin("ping\n").await?;
+    ///
+    /// while let Some(event) = child.next().await {
+    ///     match event {
+    ///         CommandEvent::Stdout(line) => log::info!("{}", line),
+    ///         CommandEvent::Stderr(line) => log::warn!("{}", line),
+    ///         CommandEvent::Terminated(status) => log::info!("exited with {:?}", status.code),
+    ///         CommandEvent::Error(err) => log::error!("{}", err),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn spawn(&self) -> crate::Result<Child> {
+        let command = self.to_js()?;
+        let stdout = command.stdout();
+        let stderr = command.stderr();
+
+        let (tx, rx) = mpsc::unbounded::<CommandEvent>();
+
+        let handlers = [
+            event_handler(&tx, |raw| {
+                CommandEvent::Stdout(raw.as_string().unwrap_or_default())
+            }),
+            event_handler(&tx, |raw| {
+                CommandEvent::Stderr(raw.as_string().unwrap_or_default())
+            }),
+            event_handler(&tx, |raw| match serde_wasm_bindgen::from_value(raw) {
+                Ok(status) => CommandEvent::Terminated(status),
+                Err(e) => CommandEvent::Error(e.to_string()),
+            }),
+            event_handler(&tx, |raw| {
+                CommandEvent::Error(raw.as_string().unwrap_or_else(|| format!("{:?}", raw)))
+            }),
+        ];
+
+        stdout.on("data", &handlers[0]);
+        stderr.on("data", &handlers[1]);
+        command.on("close", &handlers[2]);
+        command.on("error", &handlers[3]);
+
+        let child = command.spawn().await?.unchecked_into::<inner::Child>();
+
+        Ok(Child {
+            pid: child.pid(),
+            inner: child,
+            emitters: [command.into(), stdout, stderr],
+            rx,
+            terminated: false,
+            _handlers: handlers,
+        })
+    }
+
+    fn to_js(&self) -> crate::Result<inner::Command> {
+        let args = serde_wasm_bindgen::to_value(&self.args)?;
+        let options = self.options.serialize(
+            &serde_wasm_bindgen::Serializer::new()
+                .serialize_maps_as_objects(true)
+                .serialize_missing_as_null(true),
+        )?;
+
+        Ok(inner::Command::new(&self.program, args, options))
+    }
+}
+
+fn event_handler(
+    tx: &mpsc::UnboundedSender<CommandEvent>,
+    map: impl Fn(JsValue) -> CommandEvent + 'static,
+) -> Closure<dyn FnMut(JsValue)> {
+    let tx = tx.clone();
+
+    Closure::<dyn FnMut(JsValue)>::new(move |raw| {
+        let _ = tx.unbounded_send(map(raw));
+    })
+}
+
+/// A handle to a spawned child process.
+///
+/// The handle is a stream of [`CommandEvent`]s that ends after the process terminated.
+/// Dropping it detaches the event listeners, but does **not** kill the process, use [`Child::kill`] for that.
+pub struct Child {
+    pid: u32,
+    inner: inner::Child,
+    emitters: [inner::EventEmitter; 3],
+    rx: mpsc::UnboundedReceiver<CommandEvent>,
+    terminated: bool,
+    _handlers: [Closure<dyn FnMut(JsValue)>; 4],
+}
+
+impl Child {
+    /// The process identifier of the child.
+    pub fn pid(&self) -> u32 {
+        self.pid
+    }
+
+    /// Writes `data` to the stdin of the process.
+    pub async fn write_stdin(&self, data: impl AsRef<[u8]>) -> crate::Result<()> {
+        let data = js_sys::Uint8Array::from(data.as_ref());
+
+        self.inner.write(data.into()).await?;
+
+        Ok(())
+    }
+
+    /// Kills the process.
+    pub async fn kill(&self) -> crate::Result<()> {
+        self.inner.kill().await?;
+
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for Child {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Child")
+            .field("pid", &self.pid)
+            .field("terminated", &self.terminated)
+            .finish()
+    }
+}
+
+impl Drop for Child {
+    fn drop(&mut self) {
+        log::debug!("Removing event listeners for child {}", self.pid);
+        for emitter in &self.emitters {
+            emitter.removeAllListeners();
+        }
+    }
+}
+
+impl Stream for Child {
+    type Item = CommandEvent;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        if self.terminated {
+            return std::task::Poll::Ready(None);
+        }
+
+        let poll = self.rx.poll_next_unpin(cx);
+
+        if let std::task::Poll::Ready(Some(CommandEvent::Terminated(_))) = &poll {
+            self.terminated = true;
+        }
+
+        poll
+    }
+}
+
+/// Opens a path or URL with the system's default app, or the one specified with `with`.
+///
+/// The `with` value must be one of `firefox`, `google chrome`, `chromium` `safari`, `open`, `start`, `xdg-open`, `gio`, `gnome-open`, `kde-open` or `wslview`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tauri_sys::shell::open;
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// // opens the given URL on the default browser:
+/// open("https://github.com/tauri-apps/tauri", None).await?;
+/// // opens the given URL using `firefox`:
+/// open("https://github.com/tauri-apps/tauri", Some("firefox")).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn open(path: &str, with: Option<&str>) -> crate::Result<()> {
+    inner::open(path, with).await?;
+
+    Ok(())
+}
+
+mod inner {
+    use wasm_bindgen::{
+        prelude::{wasm_bindgen, Closure},
+        JsValue,
+    };
+
+    #[wasm_bindgen(module = "/src/shell.js")]
+    extern "C" {
+        #[derive(Debug, Clone)]
+        pub type EventEmitter;
+        #[wasm_bindgen(method)]
+        pub fn on(this: &EventEmitter, eventName: &str, listener: &Closure<dyn FnMut(JsValue)>);
+        #[wasm_bindgen(method)]
+        pub fn removeAllListeners(this: &EventEmitter);
+    }
+
+    #[wasm_bindgen(module = "/src/shell.js")]
+    extern "C" {
+        #[wasm_bindgen(extends = EventEmitter)]
+        #[derive(Debug, Clone)]
+        pub type Command;
+        #[wasm_bindgen(constructor)]
+        pub fn new(program: &str, args: JsValue, options: JsValue) -> Command;
+        #[wasm_bindgen(method, getter)]
+        pub fn stdout(this: &Command) -> EventEmitter;
+        #[wasm_bindgen(method, getter)]
+        pub fn stderr(this: &Command) -> EventEmitter;
+        #[wasm_bindgen(method, catch)]
+        pub async fn spawn(this: &Command) -> Result<JsValue, JsValue>;
+        #[wasm_bindgen(method, catch)]
+        pub async fn execute(this: &Command) -> Result<JsValue, JsValue>;
+    }
+
+    #[wasm_bindgen(module = "/src/shell.js")]
+    extern "C" {
+        #[derive(Debug, Clone)]
+        pub type Child;
+        #[wasm_bindgen(method, getter)]
+        pub fn pid(this: &Child) -> u32;
+        #[wasm_bindgen(method, catch)]
+        pub async fn write(this: &Child, data: JsValue) -> Result<JsValue, JsValue>;
+        #[wasm_bindgen(method, catch)]
+        pub async fn kill(this: &Child) -> Result<JsValue, JsValue>;
+    }
+
+    #[wasm_bindgen(module = "/src/shell.js")]
+    extern "C" {
+        #[wasm_bindgen(catch)]
+        pub async fn open(path: &str, openWith: Option<&str>) -> Result<JsValue, JsValue>;
+    }
+}
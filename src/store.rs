@@ -1,6 +1,6 @@
 //! Simple, persistent key-value store.
 
-use crate::event::Listen;
+use crate::event::{deserialize_or_log, Listen};
 use futures::{channel::mpsc, Stream};
 use serde::{de::DeserializeOwned, Serialize};
 use std::path::PathBuf;
@@ -205,10 +205,12 @@ impl Store {
         let (tx, rx) = mpsc::unbounded::<KeyValuePair<T>>();
 
         let closure = Closure::<dyn FnMut(JsValue, JsValue)>::new(move |key, value| {
-            let _ = tx.unbounded_send(KeyValuePair {
-                key: serde_wasm_bindgen::from_value(key).unwrap(),
-                value: serde_wasm_bindgen::from_value(value).unwrap_or(None),
-            });
+            if let Some(key) = deserialize_or_log(key) {
+                let _ = tx.unbounded_send(KeyValuePair {
+                    key,
+                    value: serde_wasm_bindgen::from_value(value).unwrap_or(None),
+                });
+            }
         });
         let unlisten = self.0.onChange(&closure).await?;
         closure.forget();
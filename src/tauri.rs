@@ -1,7 +1,9 @@
 //! Invoke your custom commands.
 
 use serde::{de::DeserializeOwned, Serialize};
+use std::{future::Future, pin::Pin};
 use url::Url;
+use wasm_bindgen::JsValue;
 
 /// Convert a device file path to an URL that can be loaded by the webview.
 ///
@@ -64,6 +66,235 @@ pub async fn invoke<A: Serialize, R: DeserializeOwned>(cmd: &str, args: &A) -> c
     serde_wasm_bindgen::from_value(raw).map_err(Into::into)
 }
 
+/// A command defined by the backend.
+///
+/// Implementing this trait ties the command name, its arguments and its return type together,
+/// so they can't silently drift apart at the call sites. It is usually implemented through the [`command!`](crate::command) macro.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use serde::{Deserialize, Serialize};
+/// use tauri_sys::tauri::Command;
+///
+/// #[derive(Serialize)]
+/// struct LoginArgs {
+///     user: String,
+///     password: String,
+/// }
+///
+/// #[derive(Debug, Deserialize)]
+/// enum LoginError {
+///     InvalidCredentials,
+///     Locked,
+/// }
+///
+/// struct Login;
+///
+/// impl Command for Login {
+///     const NAME: &'static str = "login";
+///     type Args = LoginArgs;
+///     type Output = String;
+///     type Error = LoginError;
+/// }
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let token = Login::invoke(LoginArgs {
+///     user: "tauri".to_string(),
+///     password: "poiwe3h4r5ip3yrhtew9ty".to_string(),
+/// })
+/// .await;
+/// # Ok(())
+/// # }
+/// ```
+pub trait Command {
+    /// The name of the command, as registered with the backend.
+    const NAME: &'static str;
+    /// The arguments of the command.
+    type Args: Serialize;
+    /// The value returned by the command.
+    type Output: DeserializeOwned;
+    /// The error returned by the command.
+    type Error: DeserializeOwned;
+
+    /// Invokes the command with the given arguments.
+    #[allow(clippy::type_complexity)]
+    fn invoke(
+        args: Self::Args,
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Output, CommandError<Self::Error>>>>> {
+        let args = serde_wasm_bindgen::to_value(&args);
+
+        Box::pin(async move { invoke_with_error_raw(Self::NAME, args?).await })
+    }
+}
+
+/// The error returned when invoking a [`Command`] or calling [`invoke_with_error`].
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum CommandError<E> {
+    /// The command returned an error.
+    #[error("{error}")]
+    Command {
+        /// The deserialized error.
+        error: E,
+        /// The raw value the command was rejected with, useful for logging.
+        raw: JsValue,
+    },
+    /// The command could not be invoked or its response could not be deserialized.
+    #[error(transparent)]
+    Tauri(#[from] crate::Error),
+}
+
+impl<E> CommandError<E> {
+    /// Returns the error returned by the command, if any.
+    pub fn command_error(&self) -> Option<&E> {
+        match self {
+            Self::Command { error, .. } => Some(error),
+            Self::Tauri(_) => None,
+        }
+    }
+
+    /// Consumes this error, returning the error returned by the command, if any.
+    pub fn into_command_error(self) -> Option<E> {
+        match self {
+            Self::Command { error, .. } => Some(error),
+            Self::Tauri(_) => None,
+        }
+    }
+
+    /// Returns the raw value the command was rejected with, if any.
+    pub fn raw(&self) -> Option<&JsValue> {
+        match self {
+            Self::Command { raw, .. } => Some(raw),
+            Self::Tauri(_) => None,
+        }
+    }
+}
+
+impl<E> From<serde_wasm_bindgen::Error> for CommandError<E> {
+    fn from(e: serde_wasm_bindgen::Error) -> Self {
+        Self::Tauri(e.into())
+    }
+}
+
+/// Sends a message to the backend. Returns the deserializable from the response,
+/// or the error the command was rejected with deserialized into `E`.
+///
+/// Rejections that can't be deserialized into `E` are returned as [`CommandError::Tauri`].
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use serde::{Deserialize, Serialize};
+/// use tauri_sys::tauri::{invoke_with_error, CommandError};
+///
+/// #[derive(Serialize)]
+/// struct User<'a> {
+///     user: &'a str,
+///     password: &'a str
+/// }
+///
+/// #[derive(Debug, Deserialize)]
+/// enum LoginError {
+///     InvalidCredentials,
+///     Locked,
+/// }
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let res = invoke_with_error::<_, String, LoginError>("login", &User { user: "tauri", password: "poiwe3h4r5ip3yrhtew9ty" }).await;
+///
+/// if let Err(CommandError::Command { error, raw }) = res {
+///     log::error!("Login failed with {:?} ({:?})", error, raw);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn invoke_with_error<A: Serialize, R: DeserializeOwned, E: DeserializeOwned>(
+    cmd: &str,
+    args: &A,
+) -> Result<R, CommandError<E>> {
+    invoke_with_error_raw(cmd, serde_wasm_bindgen::to_value(args)?).await
+}
+
+async fn invoke_with_error_raw<R: DeserializeOwned, E: DeserializeOwned>(
+    cmd: &str,
+    args: JsValue,
+) -> Result<R, CommandError<E>> {
+    match inner::invoke(cmd, args).await {
+        Ok(raw) => Ok(serde_wasm_bindgen::from_value(raw)?),
+        Err(raw) => match serde_wasm_bindgen::from_value(raw.clone()) {
+            Ok(error) => Err(CommandError::Command { error, raw }),
+            Err(_) => Err(CommandError::Tauri(raw.into())),
+        },
+    }
+}
+
+/// Declares [`Command`]s.
+///
+/// Each declaration creates a unit struct implementing [`Command`] from the command name, the argument type and the return type.
+/// The error type defaults to `String` when omitted.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use serde::{Deserialize, Serialize};
+/// use tauri_sys::tauri::Command;
+///
+/// #[derive(Serialize)]
+/// struct GreetArgs<'a> {
+///     name: &'a str,
+/// }
+///
+/// #[derive(Debug, Deserialize)]
+/// struct DbError {
+///     message: String,
+/// }
+///
+/// tauri_sys::command! {
+///     /// Greets the user.
+///     pub Greet = "greet" (GreetArgs<'static>) -> String;
+///     pub ListUsers = "list_users" (()) -> Vec<String>, DbError;
+/// }
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let greeting = Greet::invoke(GreetArgs { name: "tauri" }).await?;
+/// let users = ListUsers::invoke(()).await;
+/// # Ok(())
+/// # }
+/// ```
+#[macro_export]
+macro_rules! command {
+    () => {};
+    (
+        $(#[$meta:meta])*
+        $vis:vis $ident:ident = $name:literal ($args:ty) -> $output:ty;
+        $($rest:tt)*
+    ) => {
+        $crate::command! {
+            $(#[$meta])*
+            $vis $ident = $name ($args) -> $output, ::std::string::String;
+            $($rest)*
+        }
+    };
+    (
+        $(#[$meta:meta])*
+        $vis:vis $ident:ident = $name:literal ($args:ty) -> $output:ty, $error:ty;
+        $($rest:tt)*
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        $vis struct $ident;
+
+        impl $crate::tauri::Command for $ident {
+            const NAME: &'static str = $name;
+            type Args = $args;
+            type Output = $output;
+            type Error = $error;
+        }
+
+        $crate::command! { $($rest)* }
+    };
+}
+
 /// Sends a message to the backend. Returns the bytes from the response.
 ///
 /// ```rust,no_run
@@ -188,35 +188,27 @@ class WindowManager extends WindowHandle {
   }
 
   async innerPosition() {
-    return window
-      .__TAURI_INVOKE__("plugin:window|inner_position", {
-        label: this.label,
-      })
-      .then(({ x, y }) => new PhysicalPosition(x, y));
+    return invoke("plugin:window|inner_position", {
+      label: this.label,
+    }).then(({ x, y }) => new PhysicalPosition(x, y));
   }
 
   async outerPosition() {
-    return window
-      .__TAURI_INVOKE__("plugin:window|outer_position", {
-        label: this.label,
-      })
-      .then(({ x, y }) => new PhysicalPosition(x, y));
+    return invoke("plugin:window|outer_position", {
+      label: this.label,
+    }).then(({ x, y }) => new PhysicalPosition(x, y));
   }
 
   async innerSize() {
-    return window
-      .__TAURI_INVOKE__("plugin:window|inner_size", {
-        label: this.label,
-      })
-      .then(({ width, height }) => new PhysicalSize(width, height));
+    return invoke("plugin:window|inner_size", {
+      label: this.label,
+    }).then(({ width, height }) => new PhysicalSize(width, height));
   }
 
   async outerSize() {
-    return window
-      .__TAURI_INVOKE__("plugin:window|outer_size", {
-        label: this.label,
-      })
-      .then(({ width, height }) => new PhysicalSize(width, height));
+    return invoke("plugin:window|outer_size", {
+      label: this.label,
+    }).then(({ width, height }) => new PhysicalSize(width, height));
   }
 
   async isFullscreen() {
@@ -666,13 +658,12 @@ class Window extends WindowManager {
     super(label);
 
     if (!options?.skip) {
-      window
-        .__TAURI_INVOKE__("plugin:window|create", {
-          options: {
-            ...options,
-            label,
-          },
-        })
+      invoke("plugin:window|create", {
+        options: {
+          ...options,
+          label,
+        },
+      })
         .then(async () => this.emit("tauri://created"))
         .catch(async (e) => this.emit("tauri://error", e));
     }
@@ -1,16 +1,24 @@
 //! Provides APIs to create windows, communicate with other windows and manipulate the current window.
 
 use crate::{
-    event::{Event, Listen, Once},
+    event::{deserialize_or_log, Event, Listen, Once, TypedEvent},
     utils::ArrayIterator,
 };
 use futures::{
     channel::{mpsc, oneshot},
-    Stream,
+    future::{self, AbortHandle, FutureExt, LocalBoxFuture},
+    stream, Stream, StreamExt,
 };
 use js_sys::Array;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use std::fmt::Display;
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    fmt::Display,
+    future::Future,
+    path::PathBuf,
+    rc::Rc,
+};
 use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -688,18 +696,64 @@ impl Window {
         Ok(())
     }
 
+    /// Emits a [`TypedEvent`] to the backend, tied to the webview window.
+    #[inline(always)]
+    pub async fn emit_typed<E: TypedEvent>(&self, payload: &E::Payload) -> crate::Result<()> {
+        self.emit(E::NAME, payload).await
+    }
+
+    /// Listen to a [`TypedEvent`] emitted by the backend that is tied to the webview window.
+    ///
+    /// The returned Future will automatically clean up it's underlying event listener when dropped, so no manual unlisten function needs to be called.
+    #[inline(always)]
+    pub async fn listen_typed<E: TypedEvent>(
+        &self,
+    ) -> crate::Result<impl Stream<Item = Event<E::Payload>>> {
+        self.listen(E::NAME).await
+    }
+
     /// Listen to an event emitted by the backend that is tied to the webview window.
     ///
     /// The returned Future will automatically clean up it's underlying event listener when dropped, so no manual unlisten function needs to be called.
+    ///
+    /// Events whose payload can't be deserialized into `T` are logged and skipped, use [`Window::try_listen`] to receive them as errors instead.
     #[inline(always)]
-    pub async fn listen<T, H>(&self, event: &str) -> crate::Result<impl Stream<Item = Event<T>>>
+    pub async fn listen<T>(&self, event: &str) -> crate::Result<impl Stream<Item = Event<T>>>
     where
         T: DeserializeOwned + 'static,
     {
         let (tx, rx) = mpsc::unbounded::<Event<T>>();
 
         let closure = Closure::<dyn FnMut(JsValue)>::new(move |raw| {
-            let _ = tx.unbounded_send(serde_wasm_bindgen::from_value(raw).unwrap());
+            if let Some(event) = deserialize_or_log(raw) {
+                let _ = tx.unbounded_send(event);
+            }
+        });
+        let unlisten = self.0.listen(event, &closure).await?;
+        closure.forget();
+
+        Ok(Listen {
+            rx,
+            unlisten: js_sys::Function::from(unlisten),
+        })
+    }
+
+    /// Listen to an event emitted by the backend that is tied to the webview window,
+    /// yielding an error for every event whose payload can't be deserialized into `T`.
+    ///
+    /// The returned Future will automatically clean up it's underlying event listener when dropped, so no manual unlisten function needs to be called.
+    #[inline(always)]
+    pub async fn try_listen<T>(
+        &self,
+        event: &str,
+    ) -> crate::Result<impl Stream<Item = crate::Result<Event<T>>>>
+    where
+        T: DeserializeOwned + 'static,
+    {
+        let (tx, rx) = mpsc::unbounded::<crate::Result<Event<T>>>();
+
+        let closure = Closure::<dyn FnMut(JsValue)>::new(move |raw| {
+            let _ = tx.unbounded_send(serde_wasm_bindgen::from_value(raw).map_err(Into::into));
         });
         let unlisten = self.0.listen(event, &closure).await?;
         closure.forget();
@@ -713,15 +767,17 @@ impl Window {
     /// Listen to an one-off event emitted by the backend that is tied to the webview window.
     ///
     /// The returned Future will automatically clean up it's underlying event listener when dropped, so no manual unlisten function needs to be called.
+    ///
+    /// Resolves to an error if the payload of the event can't be deserialized into `T`.
     #[inline(always)]
     pub async fn once<T>(&self, event: &str) -> crate::Result<Event<T>>
     where
         T: DeserializeOwned + 'static,
     {
-        let (tx, rx) = oneshot::channel::<Event<T>>();
+        let (tx, rx) = oneshot::channel::<crate::Result<Event<T>>>();
 
         let closure: Closure<dyn FnMut(JsValue)> = Closure::once(move |raw| {
-            let _ = tx.send(serde_wasm_bindgen::from_value(raw).unwrap());
+            let _ = tx.send(serde_wasm_bindgen::from_value(raw).map_err(Into::into));
         });
         let unlisten = self.0.once(event, &closure).await?;
         closure.forget();
@@ -733,6 +789,407 @@ impl Window {
 
         fut.await
     }
+
+    /// Listen to window resize.
+    ///
+    /// The returned Future will automatically clean up it's underlying event listener when dropped, so no manual unlisten function needs to be called.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use futures::StreamExt;
+    /// use tauri_sys::window::current_window;
+    ///
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut events = current_window().on_resized().await?;
+    ///
+    /// while let Some(size) = events.next().await {
+    ///     log::debug!("Window resized to {}x{}", size.width(), size.height());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn on_resized(&self) -> crate::Result<impl Stream<Item = PhysicalSize>> {
+        let events = self.listen::<SizePayload>("tauri://resize").await?;
+
+        Ok(events.map(|event| PhysicalSize::new(event.payload.width, event.payload.height)))
+    }
+
+    /// Listen to window move.
+    ///
+    /// The returned Future will automatically clean up it's underlying event listener when dropped, so no manual unlisten function needs to be called.
+    pub async fn on_moved(&self) -> crate::Result<impl Stream<Item = PhysicalPosition>> {
+        let events = self.listen::<PositionPayload>("tauri://move").await?;
+
+        Ok(events.map(|event| PhysicalPosition::new(event.payload.x, event.payload.y)))
+    }
+
+    /// Listen to window focus change, yielding `true` when the window gained focus and `false` when it lost focus.
+    ///
+    /// The returned Future will automatically clean up it's underlying event listeners when dropped, so no manual unlisten function needs to be called.
+    pub async fn on_focus_changed(&self) -> crate::Result<impl Stream<Item = bool>> {
+        let focus = self.listen::<()>("tauri://focus").await?;
+        let blur = self.listen::<()>("tauri://blur").await?;
+
+        Ok(stream::select(focus.map(|_| true), blur.map(|_| false)))
+    }
+
+    /// Listen to window scale change.
+    ///
+    /// Emitted when the window's scale factor has changed, which happens when the user changes the display's resolution or moves the window to a display with a different scale factor.
+    ///
+    /// The returned Future will automatically clean up it's underlying event listener when dropped, so no manual unlisten function needs to be called.
+    pub async fn on_scale_changed(&self) -> crate::Result<impl Stream<Item = ScaleFactorChanged>> {
+        let events = self
+            .listen::<ScaleFactorChangedPayload>("tauri://scale-change")
+            .await?;
+
+        Ok(events.map(|event| ScaleFactorChanged {
+            scale_factor: event.payload.scale_factor,
+            size: PhysicalSize::new(event.payload.size.width, event.payload.size.height),
+        }))
+    }
+
+    /// Listen to the system theme change.
+    ///
+    /// The returned Future will automatically clean up it's underlying event listener when dropped, so no manual unlisten function needs to be called.
+    pub async fn on_theme_changed(&self) -> crate::Result<impl Stream<Item = Theme>> {
+        let events = self.listen::<Theme>("tauri://theme-changed").await?;
+
+        Ok(events.map(|event| event.payload))
+    }
+
+    /// Listen to files being dragged over, dropped onto or dragged out of the window.
+    ///
+    /// The returned Future will automatically clean up it's underlying event listeners when dropped, so no manual unlisten function needs to be called.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use futures::StreamExt;
+    /// use tauri_sys::window::{current_window, FileDropEvent};
+    ///
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut events = current_window().on_file_drop().await?;
+    ///
+    /// while let Some(event) = events.next().await {
+    ///     match event {
+    ///         FileDropEvent::Hovered(paths) => log::debug!("User hovering {:?}", paths),
+    ///         FileDropEvent::Dropped(paths) => log::debug!("User dropped {:?}", paths),
+    ///         FileDropEvent::Cancelled => log::debug!("File drop cancelled"),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn on_file_drop(&self) -> crate::Result<impl Stream<Item = FileDropEvent>> {
+        let hovered = self
+            .listen::<Vec<PathBuf>>("tauri://file-drop-hover")
+            .await?;
+        let dropped = self.listen::<Vec<PathBuf>>("tauri://file-drop").await?;
+        let cancelled = self.listen::<()>("tauri://file-drop-cancelled").await?;
+
+        Ok(stream::select(
+            stream::select(
+                hovered.map(|event| FileDropEvent::Hovered(event.payload)),
+                dropped.map(|event| FileDropEvent::Dropped(event.payload)),
+            ),
+            cancelled.map(|_| FileDropEvent::Cancelled),
+        ))
+    }
+
+    /// Listen to window close requested.
+    ///
+    /// Emitted when the user requests to closes the window.
+    /// The window is closed once the yielded [`CloseRequestedEvent`] is dropped, unless [`CloseRequestedEvent::prevent_default`] was called on it.
+    ///
+    /// The returned Future will automatically clean up it's underlying event listener when dropped, so no manual unlisten function needs to be called.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use futures::StreamExt;
+    /// use tauri_sys::window::current_window;
+    ///
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut events = current_window().on_close_requested().await?;
+    ///
+    /// while let Some(mut event) = events.next().await {
+    ///     if has_unsaved_changes() {
+    ///         event.prevent_default();
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn on_close_requested(
+        &self,
+    ) -> crate::Result<impl Stream<Item = CloseRequestedEvent>> {
+        let events = self.listen::<()>("tauri://close-requested").await?;
+        let window = self.clone();
+
+        Ok(events.map(move |_| CloseRequestedEvent {
+            window: window.clone(),
+            prevent_default: false,
+        }))
+    }
+
+    /// Registers a guard that decides whether this window may be closed.
+    ///
+    /// When the user requests to close the window, all guards registered for it are awaited one after another in registration order.
+    /// The first guard resolving to `false` keeps the window open and the remaining guards are skipped.
+    /// If all guards resolve to `true` the window is closed.
+    /// Close requests arriving while the guards are still being evaluated are ignored.
+    ///
+    /// The guard is unregistered when the returned [`CloseGuard`] is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tauri_sys::{dialog::MessageDialogBuilder, window::current_window};
+    ///
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let _guard = current_window()
+    ///     .add_close_guard(|_window| async {
+    ///         MessageDialogBuilder::new()
+    ///             .set_title("Unsaved changes".to_string())
+    ///             .confirm("Discard unsaved changes?".to_string())
+    ///             .await
+    ///             .unwrap_or(false)
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn add_close_guard<F, Fut>(&self, mut guard: F) -> crate::Result<CloseGuard>
+    where
+        F: FnMut(Window) -> Fut + 'static,
+        Fut: Future<Output = bool> + 'static,
+    {
+        let label = self.label();
+        let guard: Rc<RefCell<CloseGuardFn>> =
+            Rc::new(RefCell::new(move |window| guard(window).boxed_local()));
+
+        let registered = CLOSE_GUARDS.with(|guards| {
+            guards
+                .borrow_mut()
+                .get_mut(&label)
+                .map(|entry| entry.push(guard.clone()))
+        });
+
+        let id = match registered {
+            Some(id) => id,
+            None => {
+                let events = self.on_close_requested().await?;
+
+                // another guard might have registered the listener while we were waiting
+                let registered = CLOSE_GUARDS.with(|guards| {
+                    guards
+                        .borrow_mut()
+                        .get_mut(&label)
+                        .map(|entry| entry.push(guard.clone()))
+                });
+
+                match registered {
+                    Some(id) => id,
+                    None => {
+                        let (task, abort) =
+                            future::abortable(run_close_guards(self.clone(), events));
+                        wasm_bindgen_futures::spawn_local(task.map(|_| ()));
+
+                        let mut entry = CloseGuards {
+                            guards: Vec::new(),
+                            abort,
+                        };
+                        let id = entry.push(guard);
+
+                        CLOSE_GUARDS
+                            .with(|guards| guards.borrow_mut().insert(label.clone(), entry));
+
+                        id
+                    }
+                }
+            }
+        };
+
+        Ok(CloseGuard { label, id })
+    }
+}
+
+type CloseGuardFn = dyn FnMut(Window) -> LocalBoxFuture<'static, bool>;
+
+struct CloseGuards {
+    guards: Vec<(usize, Rc<RefCell<CloseGuardFn>>)>,
+    abort: AbortHandle,
+}
+
+impl CloseGuards {
+    fn push(&mut self, guard: Rc<RefCell<CloseGuardFn>>) -> usize {
+        // ids are unique across all windows and entries, so a stale `CloseGuard` never removes a newer guard
+        let id = NEXT_CLOSE_GUARD_ID.with(|next| next.replace(next.get() + 1));
+        self.guards.push((id, guard));
+        id
+    }
+}
+
+thread_local! {
+    static CLOSE_GUARDS: RefCell<HashMap<String, CloseGuards>> = RefCell::new(HashMap::new());
+    static NEXT_CLOSE_GUARD_ID: Cell<usize> = const { Cell::new(0) };
+}
+
+async fn run_close_guards(window: Window, events: impl Stream<Item = CloseRequestedEvent>) {
+    let mut events = Box::pin(events);
+
+    while let Some(mut event) = events.next().await {
+        event.prevent_default();
+
+        let guards = CLOSE_GUARDS.with(|guards| {
+            guards
+                .borrow()
+                .get(&window.label())
+                .map(|entry| {
+                    entry
+                        .guards
+                        .iter()
+                        .map(|(_, guard)| guard.clone())
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default()
+        });
+
+        let mut allowed = true;
+        for guard in guards {
+            let fut = (guard.borrow_mut())(window.clone());
+            if !fut.await {
+                allowed = false;
+                break;
+            }
+        }
+
+        // drop the requests that came in while the guards were running
+        while let Some(Some(mut event)) = events.next().now_or_never() {
+            event.prevent_default();
+        }
+
+        if allowed {
+            // stop listening first, so the backend closes the window without asking us again
+            CLOSE_GUARDS.with(|guards| guards.borrow_mut().remove(&window.label()));
+            drop(events);
+
+            if let Err(e) = window.close().await {
+                log::error!("Failed to close window {}: {}", window.label(), e);
+            }
+
+            return;
+        }
+    }
+}
+
+/// A guard registered through [`Window::add_close_guard`].
+///
+/// The guard is unregistered when this handle is dropped.
+#[derive(Debug)]
+pub struct CloseGuard {
+    label: String,
+    id: usize,
+}
+
+impl Drop for CloseGuard {
+    fn drop(&mut self) {
+        CLOSE_GUARDS.with(|guards| {
+            let mut guards = guards.borrow_mut();
+
+            if let Some(entry) = guards.get_mut(&self.label) {
+                entry.guards.retain(|(id, _)| *id != self.id);
+
+                if entry.guards.is_empty() {
+                    entry.abort.abort();
+                    guards.remove(&self.label);
+                }
+            }
+        });
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct SizePayload {
+    width: u32,
+    height: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct PositionPayload {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ScaleFactorChangedPayload {
+    scale_factor: f64,
+    size: SizePayload,
+}
+
+/// Payload of [`Window::on_scale_changed`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScaleFactorChanged {
+    /// The new window scale factor.
+    pub scale_factor: f64,
+    /// The new window size.
+    pub size: PhysicalSize,
+}
+
+/// Events emitted while files are dragged onto a window, see [`Window::on_file_drop`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileDropEvent {
+    /// The files are being dragged over the window.
+    Hovered(Vec<PathBuf>),
+    /// The files were dropped onto the window.
+    Dropped(Vec<PathBuf>),
+    /// The files were dragged out of the window again.
+    Cancelled,
+}
+
+/// A request to close a window, see [`Window::on_close_requested`].
+///
+/// The window is closed when this event is dropped, unless [`CloseRequestedEvent::prevent_default`] was called.
+/// The event may be held across `.await` points, for example to ask the user for confirmation first.
+#[derive(Debug)]
+pub struct CloseRequestedEvent {
+    window: Window,
+    prevent_default: bool,
+}
+
+impl CloseRequestedEvent {
+    /// The window that is requested to close.
+    pub fn window(&self) -> &Window {
+        &self.window
+    }
+
+    /// Prevents the window from being closed.
+    pub fn prevent_default(&mut self) {
+        self.prevent_default = true;
+    }
+
+    /// Whether [`CloseRequestedEvent::prevent_default`] was called.
+    pub fn is_default_prevented(&self) -> bool {
+        self.prevent_default
+    }
+}
+
+impl Drop for CloseRequestedEvent {
+    fn drop(&mut self) {
+        if self.prevent_default {
+            return;
+        }
+
+        let window = self.window.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            if let Err(e) = window.close().await {
+                log::error!("Failed to close window {}: {}", window.label(), e);
+            }
+        });
+    }
 }
 
 /// A position represented in logical pixels.
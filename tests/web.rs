@@ -1,7 +1,7 @@
 use serde::Deserialize;
 use serde::Serialize;
 use tauri_sys::{mocks::mock_ipc, tauri};
-use wasm_bindgen::JsError;
+use wasm_bindgen::{JsError, JsValue};
 use wasm_bindgen_test::wasm_bindgen_test;
 use wasm_bindgen_test::wasm_bindgen_test_configure;
 
@@ -54,6 +54,15 @@ fn main() {
     wasm_bindgen_test_configure!(run_in_browser);
 }
 
+/// Lets spawned tasks and pending IPC calls run.
+async fn settle() {
+    for _ in 0..20 {
+        wasm_bindgen_futures::JsFuture::from(js_sys::Promise::resolve(&JsValue::NULL))
+            .await
+            .unwrap();
+    }
+}
+
 /**
  * App module
  */
@@ -107,3 +116,1443 @@ async fn test_invoke() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[wasm_bindgen_test]
+async fn test_command_invoke() {
+    use tauri::Command;
+
+    #[derive(Serialize, Deserialize)]
+    struct DividePayload {
+        a: u32,
+        b: u32,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    enum DivideError {
+        DivisionByZero,
+    }
+
+    tauri_sys::command! {
+        Divide = "divide" (DividePayload) -> u32, DivideError;
+    }
+
+    mock_ipc(|cmd, payload| match cmd.as_str() {
+        "divide" => {
+            let args: DividePayload = serde_wasm_bindgen::from_value(payload).unwrap();
+
+            match args.a.checked_div(args.b) {
+                Some(out) => Ok(JsValue::from(out)),
+                None => Err(JsValue::from("DivisionByZero")),
+            }
+        }
+        _ => Err(JsValue::from("Unknown command")),
+    });
+
+    let out = Divide::invoke(DividePayload { a: 12, b: 4 }).await;
+    assert_eq!(out, Ok(3));
+
+    let err = Divide::invoke(DividePayload { a: 12, b: 0 })
+        .await
+        .unwrap_err();
+    assert_eq!(err.command_error(), Some(&DivideError::DivisionByZero));
+}
+
+#[wasm_bindgen_test]
+async fn test_invoke_with_error() {
+    use tauri::CommandError;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct LoginError {
+        code: u32,
+    }
+
+    mock_ipc(|cmd, _payload| match cmd.as_str() {
+        "login" => Err::<JsValue, _>(js_sys::JSON::parse(r#"{ "code": 401 }"#).unwrap()),
+        _ => Err(JsValue::from("Unknown command")),
+    });
+
+    let res = tauri::invoke_with_error::<_, String, LoginError>("login", &()).await;
+
+    match res {
+        Err(CommandError::Command { error, raw }) => {
+            assert_eq!(error, LoginError { code: 401 });
+            assert!(raw.is_object());
+        }
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+/**
+ * Event module
+ */
+
+/// Installs a router answering the event commands.
+fn install_events() -> tauri_sys::mocks::MockEvents {
+    use tauri_sys::mocks::{MockEvents, MockRouter};
+
+    let mut router = MockRouter::new();
+    let events = MockEvents::new();
+    events.register(&mut router);
+    router.install();
+
+    events
+}
+
+#[wasm_bindgen_test]
+async fn test_malformed_payloads() -> Result<(), Box<dyn std::error::Error>> {
+    use futures::StreamExt;
+    use tauri_sys::{
+        event::{listen, once, try_listen, EventOptions},
+        mocks::mock_emit,
+    };
+
+    install_events();
+
+    let mut events = listen::<u32>("progress", EventOptions::new()).await?;
+    let mut results = try_listen::<u32>("progress", EventOptions::new()).await?;
+
+    mock_emit("progress", &"not a number")?;
+    mock_emit("progress", &42)?;
+
+    // the malformed payload is skipped and the stream stays alive
+    assert_eq!(events.next().await.map(|event| event.payload), Some(42));
+
+    assert!(results.next().await.unwrap().is_err());
+    assert_eq!(results.next().await.unwrap()?.payload, 42);
+
+    let event = once::<u32>("loaded", EventOptions::new());
+    let emitted = async {
+        // wait for the listener to be registered
+        settle().await;
+        mock_emit("loaded", &"not a number").unwrap();
+    };
+    let (event, _) = futures::join!(event, emitted);
+    assert!(event.is_err());
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+async fn test_listen_with_overflow() -> Result<(), Box<dyn std::error::Error>> {
+    use futures::{FutureExt, Stream, StreamExt};
+    use tauri_sys::{
+        event::{listen_with, Event, ListenOptions, Overflow},
+        mocks::mock_emit,
+    };
+
+    /// Returns the payloads of the buffered events without waiting for new ones.
+    fn buffered<T>(stream: &mut (impl Stream<Item = Event<T>> + Unpin)) -> Vec<T> {
+        let mut payloads = Vec::new();
+        while let Some(Some(event)) = stream.next().now_or_never() {
+            payloads.push(event.payload);
+        }
+        payloads
+    }
+
+    install_events();
+
+    let options = |overflow| ListenOptions {
+        capacity: 2,
+        overflow,
+        ..Default::default()
+    };
+    let mut oldest = listen_with::<u32>("tick", options(Overflow::DropOldest)).await?;
+    let mut newest = listen_with::<u32>("tick", options(Overflow::DropNewest)).await?;
+    let mut coalesce = listen_with::<u32>("tick", options(Overflow::Coalesce)).await?;
+
+    for tick in 1..=5 {
+        mock_emit("tick", &tick)?;
+    }
+
+    // only `capacity` events are buffered
+    assert_eq!(buffered(&mut oldest), [4, 5]);
+    assert_eq!(buffered(&mut newest), [1, 2]);
+    assert_eq!(buffered(&mut coalesce), [1, 5]);
+
+    // reading frees up the buffer again
+    mock_emit("tick", &6)?;
+    assert_eq!(buffered(&mut newest), [6]);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+async fn test_listen_many() -> Result<(), Box<dyn std::error::Error>> {
+    use futures::{FutureExt, StreamExt};
+    use tauri_sys::{
+        event::{listen_many, EventOptions},
+        mocks::mock_emit,
+    };
+
+    let events = install_events();
+
+    let mut jobs =
+        listen_many::<u32>(&["job:1:progress", "job:1:done"], EventOptions::new()).await?;
+    assert_eq!(events.listener_count("job:1:progress"), 1);
+    assert_eq!(events.listener_count("job:1:done"), 1);
+
+    mock_emit("job:1:progress", &10)?;
+    mock_emit("job:2:progress", &20)?;
+    mock_emit("job:1:done", &100)?;
+
+    let mut received = Vec::new();
+    while let Some(Some(event)) = jobs.next().now_or_never() {
+        received.push((event.event, event.payload));
+    }
+    received.sort();
+    assert_eq!(
+        received,
+        [
+            ("job:1:done".to_string(), 100),
+            ("job:1:progress".to_string(), 10)
+        ]
+    );
+
+    drop(jobs);
+    settle().await;
+    assert_eq!(events.listener_count("job:1:progress"), 0);
+    assert_eq!(events.listener_count("job:1:done"), 0);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+async fn test_typed_events() -> Result<(), Box<dyn std::error::Error>> {
+    use futures::StreamExt;
+    use tauri_sys::{
+        event::{emit_typed, listen_typed, EventOptions, TypedEvent},
+        mocks::{MockEvents, MockRouter, MockWindowManager},
+        window::current_window,
+    };
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Progress {
+        done: u32,
+        total: u32,
+    }
+
+    struct DownloadProgress;
+
+    impl TypedEvent for DownloadProgress {
+        const NAME: &'static str = "download-progress";
+        type Payload = Progress;
+    }
+
+    let mut router = MockRouter::new();
+    MockWindowManager::new("main").register(&mut router);
+    let events = MockEvents::new();
+    events.register(&mut router);
+    router.install();
+
+    let mut global = listen_typed::<DownloadProgress>(EventOptions::new()).await?;
+    emit_typed::<DownloadProgress>(&Progress { done: 1, total: 10 }, EventOptions::new()).await?;
+
+    let event = global.next().await.unwrap();
+    assert_eq!(event.event, "download-progress");
+    assert_eq!(event.payload, Progress { done: 1, total: 10 });
+
+    let window = current_window();
+    let mut windowed = window.listen_typed::<DownloadProgress>().await?;
+    window
+        .emit_typed::<DownloadProgress>(&Progress { done: 2, total: 10 })
+        .await?;
+
+    let event = windowed.next().await.unwrap();
+    assert_eq!(event.payload, Progress { done: 2, total: 10 });
+    assert_eq!(
+        global.next().await.map(|event| event.payload),
+        Some(Progress { done: 2, total: 10 })
+    );
+
+    let emitted = events.emitted();
+    assert_eq!(emitted.len(), 2);
+    assert!(emitted
+        .iter()
+        .all(|event| event.event == "download-progress"));
+    assert_eq!(emitted[1].window_label.as_deref(), Some("main"));
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+async fn test_event_request() -> Result<(), Box<dyn std::error::Error>> {
+    use futures::StreamExt;
+    use serde_json::Value;
+    use std::time::Duration;
+    use tauri_sys::{
+        event::{listen, request, EventOptions},
+        mocks::mock_emit,
+    };
+
+    let events = install_events();
+
+    // answers requests in reverse order, after an unrelated reply
+    let mut requests = listen::<Value>("lookup", EventOptions::new()).await?;
+    wasm_bindgen_futures::spawn_local(async move {
+        let mut pending = Vec::new();
+        while let Some(event) = requests.next().await {
+            pending.push(event.payload);
+            if pending.len() < 2 {
+                continue;
+            }
+
+            mock_emit("lookup:reply:unrelated", &"unrelated").unwrap();
+            for request in pending.drain(..).rev() {
+                let reply_to = request["replyTo"].as_str().unwrap();
+                assert!(reply_to.contains(request["id"].as_str().unwrap()));
+                mock_emit(reply_to, &format!("user {}", request["payload"])).unwrap();
+            }
+        }
+    });
+
+    let timeout = Duration::from_secs(5);
+    let (first, second) = futures::join!(
+        request::<_, String>("lookup", &1, timeout),
+        request::<_, String>("lookup", &2, timeout),
+    );
+    assert_eq!(first?, "user 1");
+    assert_eq!(second?, "user 2");
+
+    let err = request::<_, String>("unanswered", &3, Duration::from_millis(10))
+        .await
+        .unwrap_err();
+    assert_eq!(err.to_string(), "timed out");
+
+    // the reply listeners are removed after a reply and after a timeout
+    settle().await;
+    let emitted = events.emitted();
+    assert_eq!(emitted.len(), 3);
+    for event in emitted {
+        let reply_to = js_sys::Reflect::get(&event.payload, &"replyTo".into())
+            .unwrap()
+            .as_string()
+            .unwrap();
+        assert_eq!(events.listener_count(&reply_to), 0);
+    }
+
+    Ok(())
+}
+
+/**
+ * Mocks module
+ */
+
+#[wasm_bindgen_test]
+async fn test_mock_router() {
+    use tauri_sys::mocks::MockRouter;
+
+    #[derive(Serialize, Deserialize)]
+    struct AddPayload {
+        a: u32,
+        b: u32,
+    }
+
+    let calls = MockRouter::new()
+        .add_command("add", |args: AddPayload| Ok::<_, String>(args.a + args.b))
+        .install();
+
+    let out = tauri::invoke::<_, u32>("add", &AddPayload { a: 1, b: 2 }).await;
+    assert_eq!(out, Ok(3));
+
+    let out = tauri::invoke::<_, u32>("sub", &AddPayload { a: 1, b: 2 }).await;
+    assert!(out.is_err());
+
+    assert_eq!(calls.count("add"), 1);
+    assert_eq!(calls.count("sub"), 1);
+    assert_eq!(calls.all().len(), 2);
+}
+
+#[wasm_bindgen_test]
+async fn test_mock_events() -> Result<(), Box<dyn std::error::Error>> {
+    use futures::StreamExt;
+    use tauri_sys::{
+        event::{emit, listen, EventOptions},
+        mocks::{mock_emit, mock_emit_to, MockEvents, MockRouter},
+    };
+
+    let mut router = MockRouter::new();
+    let events = MockEvents::new();
+    events.register(&mut router);
+    router.install();
+
+    let mut all = listen::<u32>("rust-event", EventOptions::new()).await?;
+    let mut main = listen::<u32>(
+        "rust-event",
+        EventOptions {
+            window_label: Some("main".to_string()),
+        },
+    )
+    .await?;
+
+    mock_emit_to("other", "rust-event", &1)?;
+    mock_emit("rust-event", &2)?;
+
+    assert_eq!(all.next().await.map(|event| event.payload), Some(1));
+    assert_eq!(all.next().await.map(|event| event.payload), Some(2));
+    assert_eq!(main.next().await.map(|event| event.payload), Some(2));
+
+    emit("frontend-event", &3, EventOptions::new()).await?;
+
+    let emitted = events.emitted();
+    assert_eq!(emitted.len(), 1);
+    assert_eq!(emitted[0].event, "frontend-event");
+    assert_eq!(emitted[0].payload, JsValue::from(3));
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+async fn test_mock_events_once() -> Result<(), Box<dyn std::error::Error>> {
+    use tauri_sys::event::{emit, once, EventOptions};
+
+    let events = install_events();
+
+    // the `once` listener unlistens from within the delivery of the frontend event
+    let (event, emitted) =
+        futures::join!(once::<u32>("frontend-event", EventOptions::new()), async {
+            settle().await;
+            emit("frontend-event", &3, EventOptions::new()).await
+        });
+    emitted?;
+    assert_eq!(event?.payload, 3);
+
+    settle().await;
+    assert_eq!(events.listener_count("frontend-event"), 0);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+async fn test_mock_events_window() -> Result<(), Box<dyn std::error::Error>> {
+    use futures::{FutureExt, StreamExt};
+    use tauri_sys::{
+        mocks::{mock_emit_to, MockEvents, MockRouter, MockWindowManager},
+        window::current_window,
+    };
+
+    let mut router = MockRouter::new();
+    MockWindowManager::new("main").register(&mut router);
+    let events = MockEvents::new();
+    events.register(&mut router);
+    router.install();
+
+    let window = current_window();
+    let mut main = window.listen::<u32>("rust-event").await?;
+
+    mock_emit_to("other", "rust-event", &1)?;
+    mock_emit_to("main", "rust-event", &2)?;
+
+    assert_eq!(main.next().await.map(|event| event.payload), Some(2));
+    assert!(main.next().now_or_never().is_none());
+
+    window.emit("frontend-event", &3).await?;
+
+    let emitted = events.emitted();
+    assert_eq!(emitted.len(), 1);
+    assert_eq!(emitted[0].window_label.as_deref(), Some("main"));
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+async fn test_mock_fs() -> Result<(), Box<dyn std::error::Error>> {
+    use tauri_sys::{
+        fs::{self, BaseDirectory, ScopedPath},
+        mocks::{MockFs, MockRouter},
+    };
+
+    let mut router = MockRouter::new();
+    let mock_fs = MockFs::new();
+    mock_fs.add_file("config/settings.json", BaseDirectory::AppConfig, "{}");
+    mock_fs.register(&mut router);
+    router.install();
+
+    let config = ScopedPath::new(BaseDirectory::AppConfig, "config")?;
+
+    let settings = fs::read_text_file(&config.join("settings.json")?).await?;
+    assert_eq!(settings, "{}");
+
+    fs::write_text_file(&config.join("other.json")?, "[]").await?;
+    assert_eq!(
+        mock_fs.read("config/other.json", BaseDirectory::AppConfig),
+        Some(b"[]".to_vec())
+    );
+
+    let entries = fs::read_dir(&config).await?;
+    assert_eq!(entries.len(), 2);
+
+    // base directories are separate trees
+    assert!(!fs::exists(&ScopedPath::new(BaseDirectory::AppData, "config")?).await?);
+
+    let missing = fs::read_text_file(&config.join("missing.txt")?).await;
+    assert!(missing.is_err());
+
+    let not_empty = fs::remove_dir(&config).await;
+    assert!(not_empty.is_err());
+
+    fs::remove_dir_all(&config).await?;
+    assert!(!mock_fs.exists("config", BaseDirectory::AppConfig));
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_scoped_path() {
+    use tauri_sys::fs::{BaseDirectory, ScopedPath};
+
+    let path = ScopedPath::new(BaseDirectory::AppData, "./users/tauri/avatar.png").unwrap();
+    assert_eq!(path.base(), BaseDirectory::AppData);
+    assert_eq!(path.rel(), "users/tauri/avatar.png");
+    assert_eq!(path.file_name(), Some("avatar.png"));
+    assert_eq!(path.parent().unwrap().rel(), "users/tauri");
+
+    assert!(ScopedPath::new(BaseDirectory::AppData, "../secrets").is_err());
+    assert!(ScopedPath::new(BaseDirectory::AppData, "/etc/passwd").is_err());
+    assert!(path.join("../../../secrets").is_err());
+}
+
+#[wasm_bindgen_test]
+async fn test_fs_streaming() -> Result<(), Box<dyn std::error::Error>> {
+    use futures::{SinkExt, TryStreamExt};
+    use tauri_sys::{
+        fs::{self, BaseDirectory, FileWriter, ReadStreamOptions, ScopedPath},
+        mocks::{MockFs, MockRouter},
+    };
+
+    let mut router = MockRouter::new();
+    let mock_fs = MockFs::new();
+    mock_fs.register(&mut router);
+    router.install();
+
+    let path = ScopedPath::new(BaseDirectory::AppData, "log.txt")?;
+
+    let writer = FileWriter::create(&path).await?;
+    writer.write(b"hello ").await?;
+    let mut sink = Box::pin(writer.into_sink());
+    sink.send(b"streaming ".to_vec()).await?;
+    sink.send(b"world".to_vec()).await?;
+    sink.close().await?;
+    assert_eq!(mock_fs.open_files(), 0);
+    drop(sink);
+
+    let writer = FileWriter::append(&path).await?;
+    writer.write(b"!").await?;
+    writer.close().await?;
+    assert_eq!(mock_fs.open_files(), 0);
+
+    assert_eq!(
+        mock_fs.read("log.txt", BaseDirectory::AppData),
+        Some(b"hello streaming world!".to_vec())
+    );
+
+    let chunks: Vec<Vec<u8>> = fs::read_stream(
+        &path,
+        ReadStreamOptions {
+            offset: 6,
+            length: Some(9),
+            chunk_size: 4,
+        },
+    )
+    .await?
+    .try_collect()
+    .await?;
+    assert_eq!(
+        chunks,
+        vec![b"stre".to_vec(), b"amin".to_vec(), b"g".to_vec()]
+    );
+
+    let missing = fs::read_stream(
+        &ScopedPath::new(BaseDirectory::AppData, "missing.txt")?,
+        ReadStreamOptions::default(),
+    )
+    .await;
+    assert!(missing.is_err());
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+async fn test_fs_short_writes() -> Result<(), Box<dyn std::error::Error>> {
+    use std::{cell::RefCell, rc::Rc};
+    use tauri_sys::{
+        fs::{BaseDirectory, FileWriter, ScopedPath},
+        mocks::{MockFs, MockRouter},
+    };
+
+    #[derive(Deserialize)]
+    struct WriteArgs {
+        data: Vec<u8>,
+    }
+
+    let mut router = MockRouter::new();
+    let mock_fs = MockFs::new();
+    mock_fs.register(&mut router);
+
+    let received = Rc::new(RefCell::new(Vec::new()));
+    let sink = received.clone();
+    router.add_command("plugin:fs|write", move |args: WriteArgs| {
+        let written = args.data.len().min(4);
+        sink.borrow_mut().push(args.data[..written].to_vec());
+        Ok::<_, String>(written)
+    });
+    router.install();
+
+    let writer = FileWriter::create(&ScopedPath::new(BaseDirectory::AppData, "log.txt")?).await?;
+    writer.write(b"hello world").await?;
+    assert_eq!(
+        *received.borrow(),
+        vec![b"hell".to_vec(), b"o wo".to_vec(), b"rld".to_vec()]
+    );
+
+    received.borrow_mut().clear();
+    writer.write(b"").await?;
+    assert!(received.borrow().is_empty());
+
+    let mut router = MockRouter::new();
+    mock_fs.register(&mut router);
+    router.add_command("plugin:fs|write", |_: WriteArgs| Ok::<_, String>(0));
+    router.install();
+
+    let writer = FileWriter::create(&ScopedPath::new(BaseDirectory::AppData, "full.txt")?).await?;
+    let err = writer.write(b"hello").await.unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "failed to write whole buffer, 5 bytes left"
+    );
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+async fn test_fs_open_options() -> Result<(), Box<dyn std::error::Error>> {
+    use tauri_sys::{
+        fs::{self, BaseDirectory, OpenOptions, ScopedPath},
+        mocks::{MockFs, MockRouter},
+    };
+
+    let mut router = MockRouter::new();
+    let mock_fs = MockFs::new();
+    mock_fs.add_file("app.log", BaseDirectory::AppLog, "one\n");
+    mock_fs.register(&mut router);
+    router.install();
+
+    let log = ScopedPath::new(BaseDirectory::AppLog, "app.log")?;
+
+    OpenOptions::new()
+        .set_append(true)
+        .write(&log, b"two\n")
+        .await?;
+    assert_eq!(fs::read_text_file(&log).await?, "one\ntwo\n");
+
+    let exists = OpenOptions::new()
+        .set_create_new(true)
+        .write(&log, b"three\n")
+        .await;
+    assert!(exists.is_err());
+
+    OpenOptions::new()
+        .set_truncate(false)
+        .write(&log, b"ONE")
+        .await?;
+    assert_eq!(fs::read_text_file(&log).await?, "ONE\ntwo\n");
+
+    OpenOptions::new()
+        .set_atomic(true)
+        .set_append(true)
+        .write(&log, b"three\n")
+        .await?;
+    assert_eq!(fs::read_text_file(&log).await?, "ONE\ntwo\nthree\n");
+    assert_eq!(mock_fs.open_files(), 0);
+
+    // the temporary file was renamed over the target
+    let entries = fs::read_dir(&ScopedPath::new(BaseDirectory::AppLog, "")?).await?;
+    assert_eq!(entries.len(), 1);
+
+    OpenOptions::new().write(&log, b"").await?;
+    assert_eq!(fs::read_text_file(&log).await?, "");
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+async fn test_fs_atomic_write_failure() -> Result<(), Box<dyn std::error::Error>> {
+    use tauri_sys::{
+        fs::{self, BaseDirectory, OpenOptions, ScopedPath},
+        mocks::{MockFs, MockRouter},
+    };
+
+    #[derive(Deserialize)]
+    struct WriteArgs {}
+
+    let mut router = MockRouter::new();
+    let mock_fs = MockFs::new();
+    mock_fs.add_file("settings/old.json", BaseDirectory::AppConfig, "old");
+    mock_fs.register(&mut router);
+    router.add_command("plugin:fs|write", |_: WriteArgs| {
+        Err::<usize, _>("disk full".to_string())
+    });
+    router.install();
+
+    let settings = ScopedPath::new(BaseDirectory::AppConfig, "settings")?;
+
+    let failed = OpenOptions::new()
+        .set_atomic(true)
+        .write(&settings.join("old.json")?, b"new")
+        .await;
+    assert!(failed.is_err());
+    assert_eq!(
+        mock_fs.read("settings/old.json", BaseDirectory::AppConfig),
+        Some(b"old".to_vec())
+    );
+
+    // the path reserved for the new file is released again
+    let failed = OpenOptions::new()
+        .set_atomic(true)
+        .set_create_new(true)
+        .write(&settings.join("new.json")?, b"new")
+        .await;
+    assert!(failed.is_err());
+    assert!(!mock_fs.exists("settings/new.json", BaseDirectory::AppConfig));
+
+    // no temporary file is left behind
+    let entries = fs::read_dir(&settings).await?;
+    assert_eq!(entries.len(), 1);
+    assert_eq!(mock_fs.open_files(), 0);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+async fn test_fs_watch() -> Result<(), Box<dyn std::error::Error>> {
+    use futures::{FutureExt, StreamExt};
+    use std::path::PathBuf;
+    use tauri_sys::{
+        fs::{self, BaseDirectory, ScopedPath, WatchEvent, WatchOptions},
+        mocks::{MockFs, MockRouter},
+    };
+
+    let mut router = MockRouter::new();
+    let mock_fs = MockFs::new();
+    mock_fs.add_dir("notes", BaseDirectory::Document);
+    mock_fs.register(&mut router);
+    router.install();
+
+    let notes = ScopedPath::new(BaseDirectory::Document, "notes")?;
+    let mut events = fs::watch(&notes, WatchOptions::default()).await?;
+    assert_eq!(mock_fs.watchers(), 1);
+
+    mock_fs.add_file("notes/todo.md", BaseDirectory::Document, "- [ ] test");
+    fs::write_text_file(&notes.join("todo.md")?, "- [x] test").await?;
+    fs::rename_file(&notes.join("todo.md")?, &notes.join("done.md")?).await?;
+    fs::remove_file(&notes.join("done.md")?).await?;
+
+    // changes outside of the watched directory are not reported
+    mock_fs.add_file("other.md", BaseDirectory::Document, "");
+
+    let todo = PathBuf::from("notes/todo.md");
+    let done = PathBuf::from("notes/done.md");
+    assert_eq!(events.next().await, Some(WatchEvent::Created(todo.clone())));
+    assert_eq!(
+        events.next().await,
+        Some(WatchEvent::Modified(todo.clone()))
+    );
+    assert_eq!(
+        events.next().await,
+        Some(WatchEvent::Renamed {
+            from: todo,
+            to: done.clone()
+        })
+    );
+    assert_eq!(events.next().await, Some(WatchEvent::Removed(done)));
+    assert!(events.next().now_or_never().is_none());
+
+    drop(events);
+    // let the unwatch command run
+    fs::exists(&notes).await?;
+    assert_eq!(mock_fs.watchers(), 0);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+async fn test_fs_walk() -> Result<(), Box<dyn std::error::Error>> {
+    use futures::TryStreamExt;
+    use tauri_sys::{
+        fs::{self, BaseDirectory, ScopedPath, WalkOptions},
+        mocks::{MockFs, MockRouter},
+    };
+
+    let mut router = MockRouter::new();
+    let mock_fs = MockFs::new();
+    mock_fs
+        .add_file("project/README.md", BaseDirectory::Home, "# Project")
+        .add_file("project/src/main.rs", BaseDirectory::Home, "fn main() {}")
+        .add_file("project/src/lib.rs", BaseDirectory::Home, "")
+        .add_file("project/src/util/mod.rs", BaseDirectory::Home, "")
+        .add_file("project/target/debug/app", BaseDirectory::Home, "");
+    mock_fs.register(&mut router);
+    let calls = router.install();
+
+    let project = ScopedPath::new(BaseDirectory::Home, "project")?;
+
+    let mut options = WalkOptions::new();
+    options.add_include("**/*.rs")?.add_exclude("target")?;
+    let sources = fs::walk(&project, options)
+        .map_ok(|entry| (entry.path.rel().to_string(), entry.depth))
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(
+        sources,
+        [
+            ("project/src/lib.rs".to_string(), 2),
+            ("project/src/main.rs".to_string(), 2),
+            ("project/src/util/mod.rs".to_string(), 3),
+        ]
+    );
+    // excluded directories are never read
+    assert_eq!(calls.to("plugin:fs|read_dir").len(), 3);
+
+    let mut options = WalkOptions::new();
+    options.set_max_depth(Some(1)).set_metadata(true);
+    let entries = fs::walk(&project, options).try_collect::<Vec<_>>().await?;
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[0].path, project.join("README.md")?);
+    assert!(!entries[0].is_dir);
+    assert_eq!(
+        entries[0].metadata.as_ref().map(|metadata| metadata.size),
+        Some(9)
+    );
+    assert_eq!(entries[1].path, project.join("src")?);
+    assert!(entries[1].is_dir);
+
+    assert!(WalkOptions::new().add_include("[").is_err());
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+async fn test_fs_copy_dir() -> Result<(), Box<dyn std::error::Error>> {
+    use futures::{StreamExt, TryStreamExt};
+    use tauri_sys::{
+        fs::{self, BaseDirectory, CopyOptions, ScopedPath},
+        mocks::{MockFs, MockRouter},
+    };
+
+    let mut router = MockRouter::new();
+    let mock_fs = MockFs::new();
+    mock_fs
+        .add_file("project/a.txt", BaseDirectory::AppData, "aaa")
+        .add_file("project/nested/b.txt", BaseDirectory::AppData, "bb")
+        .add_file("backup/a.txt", BaseDirectory::AppData, "old");
+    mock_fs.register(&mut router);
+    router.install();
+
+    let project = ScopedPath::new(BaseDirectory::AppData, "project")?;
+    let backup = ScopedPath::new(BaseDirectory::AppData, "backup")?;
+
+    // existing files fail the copy unless they are overwritten or skipped
+    let mut progress = Box::pin(fs::copy_dir(&project, &backup, CopyOptions::default())?);
+    assert!(progress.next().await.unwrap().is_err());
+    assert!(progress.next().await.is_none());
+
+    let progress = fs::copy_dir(
+        &project,
+        &backup,
+        CopyOptions {
+            skip_existing: true,
+            ..Default::default()
+        },
+    )?
+    .try_collect::<Vec<_>>()
+    .await?;
+    assert_eq!(progress.len(), 2);
+    assert!(progress[0].skipped);
+    assert_eq!(progress[1].path, project.join("nested/b.txt")?);
+    assert_eq!((progress[1].files_done, progress[1].bytes_done), (2, 2));
+    assert_eq!(
+        mock_fs.read("backup/a.txt", BaseDirectory::AppData),
+        Some(b"old".to_vec())
+    );
+    assert_eq!(
+        mock_fs.read("backup/nested/b.txt", BaseDirectory::AppData),
+        Some(b"bb".to_vec())
+    );
+
+    // dropping the stream cancels the copy
+    let copy = ScopedPath::new(BaseDirectory::AppData, "copy")?;
+    let mut progress = Box::pin(fs::copy_dir(&project, &copy, CopyOptions::default())?);
+    progress.next().await.unwrap()?;
+    drop(progress);
+    assert!(mock_fs.exists("copy/a.txt", BaseDirectory::AppData));
+    assert!(!mock_fs.exists("copy/nested/b.txt", BaseDirectory::AppData));
+
+    let moved = ScopedPath::new(BaseDirectory::AppData, "archive/project")?;
+    let progress = fs::move_dir(&project, &moved, CopyOptions::default())?
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(progress.last().map(|progress| progress.bytes_done), Some(5));
+    assert!(!mock_fs.exists("project", BaseDirectory::AppData));
+    assert_eq!(
+        mock_fs.read("archive/project/nested/b.txt", BaseDirectory::AppData),
+        Some(b"bb".to_vec())
+    );
+
+    assert!(fs::copy_dir(&moved, &moved.join("inner")?, CopyOptions::default()).is_err());
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+async fn test_fs_temp() -> Result<(), Box<dyn std::error::Error>> {
+    use tauri_sys::{
+        fs::{self, BaseDirectory, NamedTempFile, ScopedPath, TempDir},
+        mocks::{MockFs, MockRouter},
+    };
+
+    let mut router = MockRouter::new();
+    let mock_fs = MockFs::new();
+    mock_fs.register(&mut router);
+    router.install();
+
+    let scratch = TempDir::new("scratch-").await?;
+    let path = scratch.path().clone();
+    assert_eq!(path.base(), BaseDirectory::Temp);
+    assert!(path.file_name().unwrap().starts_with("scratch-"));
+    assert!(fs::exists(&path).await?);
+
+    fs::write_text_file(&path.join("notes.txt")?, "scratch").await?;
+    drop(scratch);
+    // let the removal run
+    fs::exists(&path).await?;
+    assert!(!fs::exists(&path).await?);
+
+    let build = TempDir::new("build-").await?;
+    fs::create_dir(&build.path().join("assets")?).await?;
+    fs::write_text_file(&build.path().join("assets/app.js")?, "main()").await?;
+    let build_path = build.path().clone();
+    build
+        .persist(&ScopedPath::new(BaseDirectory::AppData, "site")?)
+        .await?;
+    assert_eq!(
+        mock_fs.read("site/assets/app.js", BaseDirectory::AppData),
+        Some(b"main()".to_vec())
+    );
+    assert!(!fs::exists(&build_path).await?);
+    // the copied files were closed before the directory was removed
+    assert_eq!(mock_fs.open_files(), 0);
+
+    let download = NamedTempFile::new("download-").await?;
+    assert_eq!(mock_fs.open_files(), 0);
+    assert_eq!(
+        fs::read_binary_file(download.path()).await?,
+        Vec::<u8>::new()
+    );
+    fs::write_text_file(download.path(), "contents").await?;
+    let download_path = download.path().clone();
+    download
+        .persist(&ScopedPath::new(BaseDirectory::Temp, "download.txt")?)
+        .await?;
+    assert_eq!(
+        mock_fs.read("download.txt", BaseDirectory::Temp),
+        Some(b"contents".to_vec())
+    );
+    assert!(!fs::exists(&download_path).await?);
+
+    let export = NamedTempFile::new("export-").await?;
+    fs::write_text_file(export.path(), "id,name").await?;
+    let export_path = export.path().clone();
+    export
+        .persist(&ScopedPath::new(BaseDirectory::Download, "export.csv")?)
+        .await?;
+    assert_eq!(
+        mock_fs.read("export.csv", BaseDirectory::Download),
+        Some(b"id,name".to_vec())
+    );
+    assert!(!fs::exists(&export_path).await?);
+    assert_eq!(mock_fs.open_files(), 0);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+async fn test_log_batching() -> Result<(), Box<dyn std::error::Error>> {
+    use js_sys::{Promise, Reflect};
+    use log::LevelFilter;
+    use tauri_sys::{log::LoggerBuilder, mocks::MockRouter};
+    use wasm_bindgen_futures::JsFuture;
+
+    let mut router = MockRouter::new();
+    router.add_raw_command("plugin:log|log", |_| Ok(JsValue::UNDEFINED));
+    let calls = router.install();
+
+    LoggerBuilder::new()
+        .set_level(LevelFilter::Info)
+        .install()
+        .expect("no other logger is installed");
+
+    let line = line!() as f64;
+    log::info!(target: "test", "one");
+    log::info!(target: "test", "two");
+    log::debug!(target: "test", "filtered");
+    log::warn!(target: "test", "three");
+
+    // let the queued records be sent one after another
+    for _ in 0..60 {
+        JsFuture::from(Promise::resolve(&JsValue::NULL))
+            .await
+            .unwrap();
+    }
+
+    // every record is sent on its own, in the order it was logged
+    let records: Vec<(Option<String>, Option<f64>)> = calls
+        .to("plugin:log|log")
+        .iter()
+        .map(|call| {
+            (
+                Reflect::get(&call.payload, &"message".into())
+                    .unwrap()
+                    .as_string(),
+                Reflect::get(&call.payload, &"line".into())
+                    .unwrap()
+                    .as_f64(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        records,
+        [
+            (Some("one".to_string()), Some(line + 1.0)),
+            (Some("two".to_string()), Some(line + 2.0)),
+            (Some("three".to_string()), Some(line + 4.0)),
+        ]
+    );
+
+    Ok(())
+}
+
+#[cfg(feature = "tracing")]
+#[wasm_bindgen_test]
+async fn test_tracing_layer() -> Result<(), Box<dyn std::error::Error>> {
+    use js_sys::{Promise, Reflect};
+    use tauri_sys::{log::TauriLayer, mocks::MockRouter};
+    use tracing_subscriber::prelude::*;
+    use wasm_bindgen_futures::JsFuture;
+
+    let mut router = MockRouter::new();
+    router.add_raw_command("plugin:log|log", |_| Ok(JsValue::UNDEFINED));
+    let calls = router.install();
+
+    let subscriber = tracing_subscriber::registry().with(TauriLayer::new());
+    tracing::subscriber::with_default(subscriber, || {
+        let span = tracing::info_span!(target: "tracing_test", "load", file = "settings.json");
+        let _guard = span.enter();
+        tracing::warn!(target: "tracing_test", entries = 3, "loaded settings");
+    });
+
+    // let the queued records be sent one after another
+    for _ in 0..60 {
+        JsFuture::from(Promise::resolve(&JsValue::NULL))
+            .await
+            .unwrap();
+    }
+
+    let records = calls.to("plugin:log|log");
+    assert_eq!(records.len(), 3);
+
+    let message = |index: usize| {
+        Reflect::get(&records[index].payload, &"message".into())
+            .unwrap()
+            .as_string()
+            .unwrap()
+    };
+    assert!(message(0).starts_with("enter load{file=settings.json} ["));
+    assert_eq!(message(1), "load: loaded settings entries=3");
+    assert!(message(2).starts_with("exit load ["));
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+async fn test_mock_window_manager() -> Result<(), Box<dyn std::error::Error>> {
+    use futures::StreamExt;
+    use tauri_sys::{
+        mocks::{MockEvents, MockRouter, MockWindowManager},
+        window::{current_window, PhysicalSize},
+    };
+
+    let mut router = MockRouter::new();
+    let windows = MockWindowManager::new("main");
+    windows.add_window("settings");
+    windows.register(&mut router);
+    MockEvents::new().register(&mut router);
+    router.install();
+
+    let window = current_window();
+    let mut resized = window.on_resized().await?;
+
+    window.set_size(PhysicalSize::new(1024, 768)).await?;
+    window.set_title("Mocked").await?;
+    window.maximize().await?;
+
+    let size = resized.next().await.unwrap();
+    assert_eq!((size.width(), size.height()), (1024, 768));
+
+    let size = window.inner_size().await?;
+    assert_eq!((size.width(), size.height()), (1024, 768));
+    assert!(window.is_maximized().await?);
+    assert_eq!(windows.state("main").unwrap().title, "Mocked");
+    assert!(!windows.state("settings").unwrap().maximized);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+async fn test_window_events() -> Result<(), Box<dyn std::error::Error>> {
+    use futures::StreamExt;
+    use serde_json::json;
+    use std::path::PathBuf;
+    use tauri_sys::{
+        mocks::{mock_emit_to, MockEvents, MockRouter, MockWindowManager},
+        window::{current_window, FileDropEvent, Theme},
+    };
+
+    let mut router = MockRouter::new();
+    let windows = MockWindowManager::new("main");
+    windows.register(&mut router);
+    MockEvents::new().register(&mut router);
+    router.install();
+
+    let window = current_window();
+
+    let mut moved = window.on_moved().await?;
+    mock_emit_to("main", "tauri://move", &json!({ "x": 10, "y": -20 }))?;
+    let position = moved.next().await.unwrap();
+    assert_eq!((position.x(), position.y()), (10, -20));
+
+    let mut focus = window.on_focus_changed().await?;
+    mock_emit_to("main", "tauri://blur", &())?;
+    assert_eq!(focus.next().await, Some(false));
+    mock_emit_to("main", "tauri://focus", &())?;
+    assert_eq!(focus.next().await, Some(true));
+
+    let mut scale = window.on_scale_changed().await?;
+    mock_emit_to(
+        "main",
+        "tauri://scale-change",
+        &json!({ "scaleFactor": 2.0, "size": { "width": 1600, "height": 1200 } }),
+    )?;
+    let changed = scale.next().await.unwrap();
+    assert_eq!(changed.scale_factor, 2.0);
+    assert_eq!((changed.size.width(), changed.size.height()), (1600, 1200));
+
+    let mut theme = window.on_theme_changed().await?;
+    mock_emit_to("main", "tauri://theme-changed", &"dark")?;
+    assert_eq!(theme.next().await, Some(Theme::Dark));
+
+    let mut file_drop = window.on_file_drop().await?;
+    let paths = vec![PathBuf::from("/tmp/a.txt")];
+    mock_emit_to("main", "tauri://file-drop-hover", &paths)?;
+    assert_eq!(
+        file_drop.next().await,
+        Some(FileDropEvent::Hovered(paths.clone()))
+    );
+    mock_emit_to("main", "tauri://file-drop-cancelled", &())?;
+    assert_eq!(file_drop.next().await, Some(FileDropEvent::Cancelled));
+    mock_emit_to("main", "tauri://file-drop", &paths)?;
+    assert_eq!(file_drop.next().await, Some(FileDropEvent::Dropped(paths)));
+
+    // events of other windows are not delivered
+    mock_emit_to("settings", "tauri://theme-changed", &"light")?;
+    mock_emit_to("main", "tauri://theme-changed", &"dark")?;
+    assert_eq!(theme.next().await, Some(Theme::Dark));
+
+    let mut close_requested = window.on_close_requested().await?;
+
+    // a prevented request keeps the window open
+    mock_emit_to("main", "tauri://close-requested", &())?;
+    let mut event = close_requested.next().await.unwrap();
+    event.prevent_default();
+    drop(event);
+    settle().await;
+    assert!(windows.state("main").is_some());
+
+    // dropping the event closes the window
+    mock_emit_to("main", "tauri://close-requested", &())?;
+    drop(close_requested.next().await.unwrap());
+    settle().await;
+    assert!(windows.state("main").is_none());
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+async fn test_window_close_guards() -> Result<(), Box<dyn std::error::Error>> {
+    use std::{cell::Cell, rc::Rc};
+    use tauri_sys::{
+        mocks::{mock_emit_to, MockEvents, MockRouter, MockWindowManager},
+        window::current_window,
+    };
+
+    let mut router = MockRouter::new();
+    let windows = MockWindowManager::new("main");
+    windows.register(&mut router);
+    let events = MockEvents::new();
+    events.register(&mut router);
+    router.install();
+
+    let window = current_window();
+    let calls = Rc::new(Cell::new(0));
+    let guard = |allow: bool| {
+        let calls = calls.clone();
+        move |_| {
+            calls.set(calls.get() + 1);
+            async move { allow }
+        }
+    };
+
+    let vetoing = window.add_close_guard(guard(false)).await?;
+    mock_emit_to("main", "tauri://close-requested", &())?;
+    settle().await;
+    assert_eq!(calls.get(), 1);
+    assert!(windows.state("main").is_some());
+
+    // dropping the last guard stops listening for close requests
+    drop(vetoing);
+    settle().await;
+    assert_eq!(events.listener_count("tauri://close-requested"), 0);
+
+    let allowing = window.add_close_guard(guard(true)).await?;
+    mock_emit_to("main", "tauri://close-requested", &())?;
+    settle().await;
+    assert_eq!(calls.get(), 2);
+    assert!(windows.state("main").is_none());
+
+    // a guard left over from the closed window doesn't unregister the guards of a new window with the same label
+    windows.add_window("main");
+    let _vetoing = window.add_close_guard(guard(false)).await?;
+    drop(allowing);
+    mock_emit_to("main", "tauri://close-requested", &())?;
+    settle().await;
+    assert_eq!(calls.get(), 3);
+    assert!(windows.state("main").is_some());
+
+    Ok(())
+}
+
+/**
+ * Http module
+ */
+
+#[wasm_bindgen_test]
+async fn test_http_request() -> Result<(), Box<dyn std::error::Error>> {
+    use tauri_sys::http::Client;
+
+    #[derive(Deserialize)]
+    struct Data {
+        answer: u32,
+    }
+
+    mock_ipc(|cmd, payload| {
+        ensure!(cmd.as_str() == "tauri", "unknown command");
+
+        let payload: ApiRequest = serde_wasm_bindgen::from_value(payload).unwrap();
+
+        ensure!(payload.__tauri_module == "Http");
+
+        match payload.message.cmd.as_str() {
+            "createClient" => Ok(JsValue::from(1)),
+            "dropClient" => Ok(JsValue::NULL),
+            "httpRequest" => Ok(js_sys::JSON::parse(
+                r#"{
+                    "url": "https://tauri.app/",
+                    "status": 200,
+                    "headers": { "content-type": "application/json" },
+                    "rawHeaders": { "content-type": ["application/json"] },
+                    "data": "{ \"answer\": 42 }"
+                }"#,
+            )
+            .unwrap()),
+            _ => Err(JsError::new("unknown command")),
+        }
+    });
+
+    let client = Client::new().await?;
+    let res = client.get("https://tauri.app/").send::<Data>().await?;
+
+    assert!(res.ok);
+    assert_eq!(res.status, 200);
+    assert_eq!(res.data.answer, 42);
+
+    Ok(())
+}
+
+/**
+ * Shell module
+ */
+
+/// Answers the `execute` command of the shell module, emitting `events` to the child before returning its pid.
+fn mock_shell(events: &'static [&'static str]) -> std::rc::Rc<std::cell::RefCell<Vec<JsValue>>> {
+    use js_sys::{Function, Reflect};
+    use std::{cell::RefCell, rc::Rc};
+    use wasm_bindgen::JsCast;
+
+    let options = Rc::new(RefCell::new(Vec::new()));
+    let recorded = options.clone();
+
+    mock_ipc(move |cmd, payload| {
+        ensure!(cmd.as_str() == "tauri", "unknown command");
+
+        let request: ApiRequest = serde_wasm_bindgen::from_value(payload.clone()).unwrap();
+        ensure!(request.__tauri_module == "Shell");
+        ensure!(request.message.cmd == "execute");
+
+        let message = Reflect::get(&payload, &"message".into()).unwrap();
+        recorded
+            .borrow_mut()
+            .push(Reflect::get(&message, &"options".into()).unwrap());
+
+        let on_event = Reflect::get(&message, &"onEventFn".into()).unwrap();
+        let handler: Function = Reflect::get(
+            &js_sys::global(),
+            &format!("_{}", on_event.as_f64().unwrap()).into(),
+        )
+        .unwrap()
+        .unchecked_into();
+
+        for event in events {
+            handler
+                .call1(&JsValue::NULL, &js_sys::JSON::parse(event).unwrap())
+                .unwrap();
+        }
+
+        Ok(JsValue::from(42))
+    });
+
+    options
+}
+
+#[wasm_bindgen_test]
+async fn test_shell_output() -> Result<(), Box<dyn std::error::Error>> {
+    use js_sys::Reflect;
+    use tauri_sys::shell::Command;
+
+    let options = mock_shell(&[
+        r#"{ "event": "Stdout", "payload": "hello" }"#,
+        r#"{ "event": "Stdout", "payload": "world" }"#,
+        r#"{ "event": "Stderr", "payload": "warning" }"#,
+        r#"{ "event": "Terminated", "payload": { "code": 1, "signal": null } }"#,
+    ]);
+
+    let output = Command::new("echo").add_arg("hello").output().await?;
+    assert_eq!(output.status.code, Some(1));
+    assert!(!output.status.success());
+    assert_eq!(output.stdout, "hello\nworld");
+    assert_eq!(output.stderr, "warning");
+
+    Command::new("echo")
+        .add_env("KEY", "value")
+        .output()
+        .await?;
+    Command::new("echo")
+        .add_env("KEY", "value")
+        .clear_env()
+        .output()
+        .await?;
+
+    let env = |index: usize| Reflect::get(&options.borrow()[index], &"env".into()).unwrap();
+    // the environment of the parent process is inherited unless it is cleared
+    assert!(env(0).is_undefined());
+    assert_eq!(
+        Reflect::get(&env(1), &"KEY".into()).unwrap().as_string(),
+        Some("value".to_string())
+    );
+    assert!(env(2).is_null());
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+async fn test_shell_spawn() -> Result<(), Box<dyn std::error::Error>> {
+    use futures::StreamExt;
+    use tauri_sys::shell::{Command, CommandEvent, ExitStatus};
+
+    mock_shell(&[
+        r#"{ "event": "Stdout", "payload": "line" }"#,
+        r#"{ "event": "Stderr", "payload": "warning" }"#,
+        r#"{ "event": "Error", "payload": "broken pipe" }"#,
+        r#"{ "event": "Terminated", "payload": { "code": null, "signal": 9 } }"#,
+        r#"{ "event": "Stdout", "payload": "after termination" }"#,
+    ]);
+
+    let child = Command::sidecar("server").spawn().await?;
+    assert_eq!(child.pid(), 42);
+
+    let events = child.collect::<Vec<_>>().await;
+    assert_eq!(
+        events,
+        [
+            CommandEvent::Stdout("line".to_string()),
+            CommandEvent::Stderr("warning".to_string()),
+            CommandEvent::Error("broken pipe".to_string()),
+            CommandEvent::Terminated(ExitStatus {
+                code: None,
+                signal: Some(9)
+            }),
+        ]
+    );
+
+    Ok(())
+}
+
+/**
+ * Cli module
+ */
+
+#[wasm_bindgen_test]
+fn test_cli_matches_parse() -> Result<(), Box<dyn std::error::Error>> {
+    use tauri_sys::cli::CliMatches;
+
+    #[derive(Deserialize)]
+    struct Args {
+        verbose: bool,
+        config: Option<String>,
+        subcommand: Option<Subcommand>,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(rename_all = "kebab-case")]
+    enum Subcommand {
+        Open { paths: Vec<String> },
+    }
+
+    let raw = js_sys::JSON::parse(
+        r#"{
+            "args": {
+                "verbose": { "value": true, "occurrences": 1 },
+                "config": { "value": null, "occurrences": 0 }
+            },
+            "subcommand": {
+                "name": "open",
+                "matches": {
+                    "args": { "paths": { "value": ["a.txt", "b.txt"], "occurrences": 2 } },
+                    "subcommand": null
+                }
+            }
+        }"#,
+    )
+    .unwrap();
+
+    let matches: CliMatches = serde_wasm_bindgen::from_value(raw)?;
+    let args: Args = matches.parse()?;
+
+    assert!(args.verbose);
+    assert_eq!(args.config, None);
+    assert_eq!(
+        args.subcommand,
+        Some(Subcommand::Open {
+            paths: vec!["a.txt".to_string(), "b.txt".to_string()]
+        })
+    );
+
+    Ok(())
+}